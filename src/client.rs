@@ -0,0 +1,1075 @@
+//! Host-side helpers for building the program's instructions, enabled with the `client` feature.
+//!
+//! The syscalls behind `pinocchio::pubkey::find_program_address` only exist on-chain, so PDAs are
+//! derived here with a host implementation of the same algorithm. Instructions use owned types
+//! whose fields mirror `solana_program::instruction::Instruction`, so converting them is a
+//! field-by-field copy.
+//!
+//! There is one builder per instruction. Each one derives the PDAs from the vault's creator and
+//! index and returns the accounts every call needs; optional accounts go at the end, in the order
+//! the instruction expects them: the allowlist for the Withdraw-style instructions on a vault that
+//! has one, then the signing members of a multisig vault for owner-authorized instructions.
+
+extern crate std;
+
+use pinocchio::pubkey::Pubkey;
+use sha2_const_stable::Sha256;
+use std::vec::Vec;
+
+use crate::{Allowlist, DelegateRecord, PendingWithdrawal, RecoveryState, VaultError, VaultState};
+
+// System Program: 11111111111111111111111111111111
+const SYSTEM_PROGRAM_ID: Pubkey = [0; 32];
+
+/// An account referenced by an instruction, with its signer and writable flags.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    pub fn new(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: true,
+        }
+    }
+
+    pub fn new_readonly(pubkey: Pubkey, is_signer: bool) -> Self {
+        Self {
+            pubkey,
+            is_signer,
+            is_writable: false,
+        }
+    }
+}
+
+/// An instruction ready to be added to a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// Derives a program address like `pinocchio::pubkey::create_program_address`, returning `None`
+/// when the seeds hash to a point on the ed25519 curve.
+pub fn create_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<Pubkey> {
+    let mut hasher = Sha256::new();
+    for seed in seeds {
+        hasher = hasher.update(seed);
+    }
+    let address = hasher
+        .update(program_id)
+        .update(b"ProgramDerivedAddress")
+        .finalize();
+
+    if curve::is_on_curve(&address) {
+        return None;
+    }
+
+    Some(address)
+}
+
+/// Finds the canonical program address and bump, like `pinocchio::pubkey::find_program_address`.
+///
+/// Returns `None` if every bump lands on the curve, which has a probability of about 2^-256 but
+/// is left to the caller rather than panicking.
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    for bump in (0..=u8::MAX).rev() {
+        let bump_seed = [bump];
+        let mut seeds_with_bump = seeds.to_vec();
+        seeds_with_bump.push(&bump_seed);
+        if let Some(address) = create_program_address(&seeds_with_bump, program_id) {
+            return Some((address, bump));
+        }
+    }
+
+    None
+}
+
+/// Vault PDA of the vault `index` created by `creator`, `["vault", creator, index]`.
+pub fn vault_address(creator: &Pubkey, index: u64) -> Option<(Pubkey, u8)> {
+    find_program_address(&[b"vault", creator, &index.to_le_bytes()], &crate::ID)
+}
+
+/// Vault PDA of the single vault `owner` had before vaults were indexed, `["vault", owner]`.
+pub fn legacy_vault_address(owner: &Pubkey) -> Option<(Pubkey, u8)> {
+    find_program_address(&[b"vault", owner], &crate::ID)
+}
+
+/// State PDA of a vault, `["state", vault]`.
+pub fn state_address(vault: &Pubkey) -> Option<(Pubkey, u8)> {
+    find_program_address(&[VaultState::SEED, vault], &crate::ID)
+}
+
+/// Delegate record PDA, `["delegate", vault, delegate]`.
+pub fn delegate_record_address(vault: &Pubkey, delegate: &Pubkey) -> Option<(Pubkey, u8)> {
+    find_program_address(&[DelegateRecord::SEED, vault, delegate], &crate::ID)
+}
+
+/// Pending withdrawal PDA, `["pending", vault, request_id]`.
+pub fn pending_withdrawal_address(vault: &Pubkey, request_id: u64) -> Option<(Pubkey, u8)> {
+    find_program_address(
+        &[PendingWithdrawal::SEED, vault, &request_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Allowlist PDA of a vault, `["allowlist", vault]`.
+pub fn allowlist_address(vault: &Pubkey) -> Option<(Pubkey, u8)> {
+    find_program_address(&[Allowlist::SEED, vault], &crate::ID)
+}
+
+/// Recovery proposal PDA of a vault, `["recovery", vault]`.
+pub fn recovery_address(vault: &Pubkey) -> Option<(Pubkey, u8)> {
+    find_program_address(&[RecoveryState::SEED, vault], &crate::ID)
+}
+
+/// Associated token account of `wallet` for `mint`, `[wallet, token_program, mint]` under the
+/// Associated Token Account program.
+pub fn associated_token_address(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Option<(Pubkey, u8)> {
+    find_program_address(
+        &[wallet, token_program, mint],
+        &crate::token::ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+}
+
+/// Builds a Deposit of `amount` lamports from `owner` into the vault `index` created by `creator`
+/// (the owner itself unless ownership was transferred).
+pub fn deposit(owner: &Pubkey, creator: &Pubkey, index: u64, amount: u64) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+
+    Some(vault_instruction(
+        *crate::Deposit::DISCRIMINATOR,
+        index,
+        &amount.to_le_bytes(),
+        std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds a Withdraw of `amount` lamports (everything with `None`) from the vault `index` created
+/// by `creator` back to `owner`.
+///
+/// Vaults with an allowlist need `allowlist_address(vault)` appended to the accounts, followed by
+/// the signing members for a multisig vault.
+pub fn withdraw(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    amount: Option<u64>,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+
+    Some(vault_instruction(
+        *crate::Withdraw::DISCRIMINATOR,
+        index,
+        &optional_amount(amount),
+        std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds a Withdraw of everything in `owner`'s legacy vault, `legacy_vault_address(owner)`.
+/// Legacy vaults have no state account, so the instruction data is the discriminator alone.
+pub fn legacy_withdraw(owner: &Pubkey) -> Option<Instruction> {
+    let (vault, _) = legacy_vault_address(owner)?;
+
+    Some(Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: std::vec![*crate::Withdraw::DISCRIMINATOR],
+    })
+}
+
+/// Builds a DepositToken of `amount` base units of `mint` from `owner_token_account` into the
+/// vault's associated token account, which must already exist.
+pub fn deposit_token(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    mint: &Pubkey,
+    owner_token_account: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (vault_token_account, _) = associated_token_address(&vault, mint, token_program)?;
+
+    Some(vault_instruction(
+        *crate::DepositToken::DISCRIMINATOR,
+        index,
+        &amount.to_le_bytes(),
+        std::vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*owner_token_account, false),
+            AccountMeta::new(vault_token_account, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    ))
+}
+
+/// Builds a WithdrawToken of `amount` base units of `mint` (everything with `None`) from the
+/// vault's associated token account to `owner_token_account`.
+///
+/// As for `withdraw`, vaults with an allowlist need `allowlist_address(vault)` appended to the
+/// accounts, followed by the signing members for a multisig vault.
+pub fn withdraw_token(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    mint: &Pubkey,
+    owner_token_account: &Pubkey,
+    token_program: &Pubkey,
+    amount: Option<u64>,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (vault_token_account, _) = associated_token_address(&vault, mint, token_program)?;
+
+    Some(vault_instruction(
+        *crate::WithdrawToken::DISCRIMINATOR,
+        index,
+        &optional_amount(amount),
+        std::vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(vault_token_account, false),
+            AccountMeta::new(*owner_token_account, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    ))
+}
+
+/// Builds an Initialize creating the state of `owner`'s vault `data.index`.
+pub fn initialize(owner: &Pubkey, data: &crate::InitializeInstructionData) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(owner, data.index)?;
+
+    let mut schedule = Vec::with_capacity(crate::InitializeInstructionData::LEN - 8);
+    schedule.extend_from_slice(&data.unlock_ts.to_le_bytes());
+    schedule.extend_from_slice(&data.start_ts.to_le_bytes());
+    schedule.extend_from_slice(&data.cliff_ts.to_le_bytes());
+    schedule.extend_from_slice(&data.end_ts.to_le_bytes());
+    schedule.extend_from_slice(&data.beneficiary);
+    schedule.extend_from_slice(&data.label);
+
+    Some(vault_instruction(
+        *crate::Initialize::DISCRIMINATOR,
+        data.index,
+        &schedule,
+        std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds a GetVested for the vault `index` created by `creator`, signed by its owner or
+/// beneficiary. The vested and withdrawable amounts come back as return data.
+pub fn get_vested(viewer: &Pubkey, creator: &Pubkey, index: u64) -> Option<Instruction> {
+    let (_, state) = vault_and_state(creator, index)?;
+
+    // GetVested reads everything from the state, so it only takes the discriminator.
+    Some(Instruction {
+        program_id: crate::ID,
+        accounts: std::vec![
+            AccountMeta::new_readonly(*viewer, true),
+            AccountMeta::new_readonly(state, false),
+        ],
+        data: std::vec![*crate::GetVested::DISCRIMINATOR],
+    })
+}
+
+/// Builds a WithdrawTo of `amount` lamports (everything with `None`) to `destination`.
+pub fn withdraw_to(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    destination: &Pubkey,
+    amount: Option<u64>,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+
+    Some(vault_instruction(
+        *crate::WithdrawTo::DISCRIMINATOR,
+        index,
+        &optional_amount(amount),
+        std::vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds an Approve giving `delegate` an allowance of `allowance` lamports until `expiry_slot`
+/// (`0` for no expiry), at most `per_tx_cap` per withdrawal (`0` for no cap).
+pub fn approve(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    delegate: &Pubkey,
+    allowance: u64,
+    expiry_slot: u64,
+    per_tx_cap: u64,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (record, _) = delegate_record_address(&vault, delegate)?;
+
+    let mut data = Vec::with_capacity(24);
+    data.extend_from_slice(&allowance.to_le_bytes());
+    data.extend_from_slice(&expiry_slot.to_le_bytes());
+    data.extend_from_slice(&per_tx_cap.to_le_bytes());
+
+    Some(vault_instruction(
+        *crate::Approve::DISCRIMINATOR,
+        index,
+        &data,
+        std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new_readonly(*delegate, false),
+            AccountMeta::new(record, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds a Revoke closing `delegate`'s record and refunding its rent to the owner.
+pub fn revoke(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    delegate: &Pubkey,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (record, _) = delegate_record_address(&vault, delegate)?;
+
+    Some(vault_instruction(
+        *crate::Revoke::DISCRIMINATOR,
+        index,
+        &[],
+        std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(record, false),
+        ],
+    ))
+}
+
+/// Builds a DelegatedWithdraw of `amount` lamports (the rest of the allowance with `None`) from
+/// the vault of `owner` to `destination`, signed by `delegate`.
+pub fn delegated_withdraw(
+    delegate: &Pubkey,
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    destination: &Pubkey,
+    amount: Option<u64>,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (record, _) = delegate_record_address(&vault, delegate)?;
+
+    Some(vault_instruction(
+        *crate::DelegatedWithdraw::DISCRIMINATOR,
+        index,
+        &optional_amount(amount),
+        std::vec![
+            AccountMeta::new_readonly(*delegate, true),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(record, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds a SetMultisig requiring `threshold` of `members` (`0` and no members to remove it).
+pub fn set_multisig(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    threshold: u8,
+    members: &[Pubkey],
+) -> Option<Instruction> {
+    let mut data = std::vec![threshold];
+    for member in members {
+        data.extend_from_slice(member);
+    }
+
+    owner_instruction(
+        *crate::SetMultisig::DISCRIMINATOR,
+        owner,
+        creator,
+        index,
+        &data,
+    )
+}
+
+/// Builds a RequestWithdraw queueing `amount` lamports to `destination` under `request_id`.
+pub fn request_withdraw(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    destination: &Pubkey,
+    request_id: u64,
+    amount: u64,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (pending, _) = pending_withdrawal_address(&vault, request_id)?;
+
+    let mut data = Vec::with_capacity(16);
+    data.extend_from_slice(&request_id.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Some(vault_instruction(
+        *crate::RequestWithdraw::DISCRIMINATOR,
+        index,
+        &data,
+        std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new_readonly(*destination, false),
+            AccountMeta::new(pending, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds an ExecuteWithdraw paying out the request `request_id` to its `destination`. Anyone can
+/// send it once the delay has passed; the pending rent goes back to `owner`.
+pub fn execute_withdraw(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    request_id: u64,
+    destination: &Pubkey,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (pending, _) = pending_withdrawal_address(&vault, request_id)?;
+
+    Some(vault_instruction(
+        *crate::ExecuteWithdraw::DISCRIMINATOR,
+        index,
+        &[],
+        std::vec![
+            AccountMeta::new(*owner, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(pending, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds a CancelWithdraw dropping the request `request_id` and refunding its rent to the owner.
+pub fn cancel_withdraw(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    request_id: u64,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (pending, _) = pending_withdrawal_address(&vault, request_id)?;
+
+    Some(vault_instruction(
+        *crate::CancelWithdraw::DISCRIMINATOR,
+        index,
+        &[],
+        std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(pending, false),
+        ],
+    ))
+}
+
+/// Builds a SetWithdrawDelay of `delay` seconds (`0` to remove it).
+pub fn set_withdraw_delay(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    delay: i64,
+) -> Option<Instruction> {
+    owner_instruction(
+        *crate::SetWithdrawDelay::DISCRIMINATOR,
+        owner,
+        creator,
+        index,
+        &delay.to_le_bytes(),
+    )
+}
+
+/// Builds a SetRateLimit of `limit` lamports per `period` (`VaultState::PERIOD_DAY` or
+/// `VaultState::PERIOD_EPOCH`), `0` to remove it.
+pub fn set_rate_limit(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    limit: u64,
+    period: u8,
+) -> Option<Instruction> {
+    let mut data = limit.to_le_bytes().to_vec();
+    data.push(period);
+
+    owner_instruction(
+        *crate::SetRateLimit::DISCRIMINATOR,
+        owner,
+        creator,
+        index,
+        &data,
+    )
+}
+
+/// Builds an AddDestination adding `destination` to the vault's allowlist, creating the allowlist
+/// on first use.
+pub fn add_destination(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    destination: &Pubkey,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (allowlist, _) = allowlist_address(&vault)?;
+
+    Some(vault_instruction(
+        *crate::AddDestination::DISCRIMINATOR,
+        index,
+        destination,
+        std::vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(allowlist, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds a RemoveDestination removing `destination` from the vault's allowlist.
+pub fn remove_destination(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    destination: &Pubkey,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (allowlist, _) = allowlist_address(&vault)?;
+
+    Some(vault_instruction(
+        *crate::RemoveDestination::DISCRIMINATOR,
+        index,
+        destination,
+        std::vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(allowlist, false),
+        ],
+    ))
+}
+
+/// Builds a TransferOwnership nominating `new_owner`, who then has to send AcceptOwnership.
+pub fn transfer_ownership(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    new_owner: &Pubkey,
+) -> Option<Instruction> {
+    owner_instruction(
+        *crate::TransferOwnership::DISCRIMINATOR,
+        owner,
+        creator,
+        index,
+        new_owner,
+    )
+}
+
+/// Builds an AcceptOwnership, signed by the nominated `new_owner`.
+pub fn accept_ownership(new_owner: &Pubkey, creator: &Pubkey, index: u64) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+
+    Some(vault_instruction(
+        *crate::AcceptOwnership::DISCRIMINATOR,
+        index,
+        &[],
+        std::vec![
+            AccountMeta::new_readonly(*new_owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+        ],
+    ))
+}
+
+/// Builds a SetGuardians requiring `threshold` of `guardians` to propose a recovery, which the
+/// owner can cancel for `challenge_period` seconds (`0` and no guardians to remove them).
+pub fn set_guardians(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    threshold: u8,
+    challenge_period: i64,
+    guardians: &[Pubkey],
+) -> Option<Instruction> {
+    let mut data = std::vec![threshold];
+    data.extend_from_slice(&challenge_period.to_le_bytes());
+    for guardian in guardians {
+        data.extend_from_slice(guardian);
+    }
+
+    owner_instruction(
+        *crate::SetGuardians::DISCRIMINATOR,
+        owner,
+        creator,
+        index,
+        &data,
+    )
+}
+
+/// Builds a ProposeRecovery handing the vault to `new_owner`, paid for by `payer` and signed by
+/// `guardians`.
+pub fn propose_recovery(
+    payer: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    new_owner: &Pubkey,
+    guardians: &[Pubkey],
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (recovery, _) = recovery_address(&vault)?;
+
+    let mut accounts = std::vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(vault, false),
+        AccountMeta::new(state, false),
+        AccountMeta::new(recovery, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    accounts.extend(
+        guardians
+            .iter()
+            .map(|guardian| AccountMeta::new_readonly(*guardian, true)),
+    );
+
+    Some(vault_instruction(
+        *crate::ProposeRecovery::DISCRIMINATOR,
+        index,
+        new_owner,
+        accounts,
+    ))
+}
+
+/// Builds a CancelRecovery closing the open proposal and refunding its rent to `payer`.
+pub fn cancel_recovery(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    payer: &Pubkey,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (recovery, _) = recovery_address(&vault)?;
+
+    Some(vault_instruction(
+        *crate::CancelRecovery::DISCRIMINATOR,
+        index,
+        &[],
+        std::vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(recovery, false),
+            AccountMeta::new(*payer, false),
+        ],
+    ))
+}
+
+/// Builds an ExecuteRecovery applying the open proposal once its challenge period has passed.
+/// Anyone can send it; the proposal's rent goes back to `payer`. The vault's multisig, if any, is
+/// removed along with the old owner.
+pub fn execute_recovery(creator: &Pubkey, index: u64, payer: &Pubkey) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+    let (recovery, _) = recovery_address(&vault)?;
+
+    Some(vault_instruction(
+        *crate::ExecuteRecovery::DISCRIMINATOR,
+        index,
+        &[],
+        std::vec![
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new(recovery, false),
+            AccountMeta::new(*payer, false),
+        ],
+    ))
+}
+
+/// Builds a Heartbeat, recording that the owner is still active.
+pub fn heartbeat(owner: &Pubkey, creator: &Pubkey, index: u64) -> Option<Instruction> {
+    owner_instruction(*crate::Heartbeat::DISCRIMINATOR, owner, creator, index, &[])
+}
+
+/// Builds a SetInheritance letting `heir` claim the vault after `inactivity_period` seconds
+/// without activity from the owner.
+pub fn set_inheritance(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    heir: &Pubkey,
+    inactivity_period: i64,
+) -> Option<Instruction> {
+    let mut data = heir.to_vec();
+    data.extend_from_slice(&inactivity_period.to_le_bytes());
+
+    owner_instruction(
+        *crate::SetInheritance::DISCRIMINATOR,
+        owner,
+        creator,
+        index,
+        &data,
+    )
+}
+
+/// Builds a Claim of `amount` lamports (everything with `None`) from the vault of an inactive
+/// `owner`, signed by and paid to `heir`.
+pub fn claim(
+    heir: &Pubkey,
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    amount: Option<u64>,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+
+    Some(vault_instruction(
+        *crate::Claim::DISCRIMINATOR,
+        index,
+        &optional_amount(amount),
+        std::vec![
+            AccountMeta::new(*heir, true),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(state, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    ))
+}
+
+/// Builds a Close draining the vault to the owner and closing its state along with `closing`, the
+/// delegate records, pending withdrawals and allowlist still open for the vault, and the vault's
+/// associated token accounts for `tokens`, given as `(mint, token_program)` pairs. Those token
+/// accounts must already be empty; token accounts left out of `tokens` aren't checked at all.
+pub fn close(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    closing: &[Pubkey],
+    tokens: &[(Pubkey, Pubkey)],
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+
+    let mut accounts = std::vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(vault, false),
+        AccountMeta::new(state, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    accounts.extend(
+        closing
+            .iter()
+            .map(|account| AccountMeta::new(*account, false)),
+    );
+    for (mint, token_program) in tokens {
+        let (token_account, _) = associated_token_address(&vault, mint, token_program)?;
+        accounts.push(AccountMeta::new(token_account, false));
+        accounts.push(AccountMeta::new_readonly(*token_program, false));
+    }
+
+    Some(vault_instruction(
+        *crate::Close::DISCRIMINATOR,
+        index,
+        &[
+            u8::try_from(closing.len()).ok()?,
+            u8::try_from(tokens.len()).ok()?,
+        ],
+        accounts,
+    ))
+}
+
+/// Builds a SetFreezeAuthority (`[0; 32]` to remove it).
+pub fn set_freeze_authority(
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    freeze_authority: &Pubkey,
+) -> Option<Instruction> {
+    owner_instruction(
+        *crate::SetFreezeAuthority::DISCRIMINATOR,
+        owner,
+        creator,
+        index,
+        freeze_authority,
+    )
+}
+
+/// Builds a Freeze, signed by the vault's freeze authority.
+pub fn freeze(freeze_authority: &Pubkey, creator: &Pubkey, index: u64) -> Option<Instruction> {
+    freeze_authority_instruction(
+        *crate::Freeze::DISCRIMINATOR,
+        freeze_authority,
+        creator,
+        index,
+    )
+}
+
+/// Builds a Thaw, signed by the vault's freeze authority.
+pub fn thaw(freeze_authority: &Pubkey, creator: &Pubkey, index: u64) -> Option<Instruction> {
+    freeze_authority_instruction(
+        *crate::Thaw::DISCRIMINATOR,
+        freeze_authority,
+        creator,
+        index,
+    )
+}
+
+// The vault and state PDAs of the vault `index` created by `creator`.
+fn vault_and_state(creator: &Pubkey, index: u64) -> Option<(Pubkey, Pubkey)> {
+    let (vault, _) = vault_address(creator, index)?;
+    let (state, _) = state_address(&vault)?;
+    Some((vault, state))
+}
+
+// An instruction with `[discriminator][index][data]`, the layout of every instruction but
+// GetVested.
+fn vault_instruction(
+    discriminator: u8,
+    index: u64,
+    data: &[u8],
+    accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut instruction_data = Vec::with_capacity(9 + data.len());
+    instruction_data.push(discriminator);
+    instruction_data.extend_from_slice(&index.to_le_bytes());
+    instruction_data.extend_from_slice(data);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: instruction_data,
+    }
+}
+
+// An owner-authorized configuration change: `[owner, vault, state]`, followed by the signing
+// members for a multisig vault.
+fn owner_instruction(
+    discriminator: u8,
+    owner: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+    data: &[u8],
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+
+    Some(vault_instruction(
+        discriminator,
+        index,
+        data,
+        std::vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+        ],
+    ))
+}
+
+// Freeze and Thaw: `[freeze_authority, vault, state]`.
+fn freeze_authority_instruction(
+    discriminator: u8,
+    freeze_authority: &Pubkey,
+    creator: &Pubkey,
+    index: u64,
+) -> Option<Instruction> {
+    let (vault, state) = vault_and_state(creator, index)?;
+
+    Some(vault_instruction(
+        discriminator,
+        index,
+        &[],
+        std::vec![
+            AccountMeta::new_readonly(*freeze_authority, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(state, false),
+        ],
+    ))
+}
+
+// The optional `[amount]` of the Withdraw-style instructions.
+fn optional_amount(amount: Option<u64>) -> Vec<u8> {
+    amount.map_or_else(Vec::new, |amount| amount.to_le_bytes().to_vec())
+}
+
+/// Decodes the code of a `Custom` program error returned by the vault.
+pub fn decode_error(code: u32) -> Option<VaultError> {
+    VaultError::try_from(code).ok()
+}
+
+// Ed25519 point decompression check, the host counterpart of the runtime's curve validation.
+// A compressed point `y` is on the curve iff `(y^2 - 1) / (d * y^2 + 1)` is a square mod
+// `p = 2^255 - 19`, i.e. iff `(y^2 - 1) * (d * y^2 + 1)` is zero or a quadratic residue.
+mod curve {
+    // Field elements mod 2^255 - 19 as five 51-bit limbs, least significant first.
+    type Fe = [u64; 5];
+
+    const MASK: u64 = (1 << 51) - 1;
+
+    const ONE: Fe = [1, 0, 0, 0, 0];
+
+    // d = -121665 / 121666
+    const D: [u8; 32] = [
+        0xa3, 0x78, 0x59, 0x13, 0xca, 0x4d, 0xeb, 0x75, 0xab, 0xd8, 0x41, 0x41, 0x4d, 0x0a, 0x70,
+        0x00, 0x98, 0xe8, 0x79, 0x77, 0x79, 0x40, 0xc7, 0x8c, 0x73, 0xfe, 0x6f, 0x2b, 0xee, 0x6c,
+        0x03, 0x52,
+    ];
+
+    // (p - 1) / 2 = 2^254 - 10, little-endian.
+    const HALF_P_MINUS_ONE: [u8; 32] = [
+        0xf6, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0x3f,
+    ];
+
+    pub fn is_on_curve(bytes: &[u8; 32]) -> bool {
+        let y = from_bytes(bytes);
+        let yy = mul(&y, &y);
+        let u = sub(&yy, &ONE);
+        let v = add(&mul(&from_bytes(&D), &yy), &ONE);
+
+        // Euler's criterion: (u * v)^((p - 1) / 2) is 0 for zero, 1 for squares and -1 otherwise.
+        let legendre = canonical(&pow(&mul(&u, &v), &HALF_P_MINUS_ONE));
+        legendre.eq(&[0; 5]) || legendre.eq(&ONE)
+    }
+
+    // Loads the low 255 bits; the top bit is the sign of x and doesn't affect the check.
+    fn from_bytes(bytes: &[u8; 32]) -> Fe {
+        let load = |offset: usize| {
+            let mut word = [0; 8];
+            let end = (offset + 8).min(32);
+            word[..end - offset].copy_from_slice(&bytes[offset..end]);
+            u64::from_le_bytes(word)
+        };
+
+        [
+            load(0) & MASK,
+            (load(6) >> 3) & MASK,
+            (load(12) >> 6) & MASK,
+            (load(19) >> 1) & MASK,
+            (load(24) >> 12) & MASK,
+        ]
+    }
+
+    // Propagates carries so every limb fits in 51 bits again (the value may still be >= p).
+    fn carry(mut a: [u128; 5]) -> Fe {
+        for i in 0..4 {
+            a[i + 1] += a[i] >> 51;
+            a[i] &= MASK as u128;
+        }
+        a[0] += 19 * (a[4] >> 51);
+        a[4] &= MASK as u128;
+        a[1] += a[0] >> 51;
+        a[0] &= MASK as u128;
+
+        a.map(|limb| limb as u64)
+    }
+
+    fn add(a: &Fe, b: &Fe) -> Fe {
+        carry(core::array::from_fn(|i| (a[i] + b[i]) as u128))
+    }
+
+    fn sub(a: &Fe, b: &Fe) -> Fe {
+        // Adding 2p first keeps every limb positive.
+        const TWO_P: Fe = [2 * ((1 << 51) - 19), 2 * MASK, 2 * MASK, 2 * MASK, 2 * MASK];
+        carry(core::array::from_fn(|i| (a[i] + TWO_P[i] - b[i]) as u128))
+    }
+
+    fn mul(a: &Fe, b: &Fe) -> Fe {
+        // Limb products landing at 2^255 and above wrap around multiplied by 19.
+        let mut t = [0u128; 5];
+        for i in 0..5 {
+            for j in 0..5 {
+                let product = a[i] as u128 * b[j] as u128;
+                if i + j < 5 {
+                    t[i + j] += product;
+                } else {
+                    t[i + j - 5] += 19 * product;
+                }
+            }
+        }
+        carry(t)
+    }
+
+    fn pow(base: &Fe, exponent: &[u8; 32]) -> Fe {
+        let mut result = ONE;
+        for byte in exponent.iter().rev() {
+            for bit in (0..8).rev() {
+                result = mul(&result, &result);
+                if (byte >> bit) & 1 == 1 {
+                    result = mul(&result, base);
+                }
+            }
+        }
+        result
+    }
+
+    // Fully reduces `a` below p, so that equal elements have equal limbs.
+    fn canonical(a: &Fe) -> Fe {
+        let mut a = carry(a.map(|limb| limb as u128));
+
+        // q is 1 iff a >= p, i.e. iff a + 19 overflows 2^255.
+        let mut q = (a[0] + 19) >> 51;
+        for limb in &a[1..] {
+            q = (limb + q) >> 51;
+        }
+
+        a[0] += 19 * q;
+        for i in 0..4 {
+            a[i + 1] += a[i] >> 51;
+            a[i] &= MASK;
+        }
+        a[4] &= MASK;
+        a
+    }
+}
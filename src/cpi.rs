@@ -0,0 +1,153 @@
+//! Typed helpers for other on-chain programs to call the vault, enabled with the `cpi` feature.
+//!
+//! Depend on the crate with `features = ["cpi"]`, which also turns on `no-entrypoint` so the
+//! vault's entrypoint doesn't clash with the caller's. When the owner is a PDA of the calling
+//! program, use `invoke_signed` with its seeds.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Signer},
+    program::invoke_signed,
+    ProgramResult,
+};
+
+/// Deposit lamports into a vault.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Owner
+///   1. `[WRITE]` Vault
+///   2. `[WRITE]` Vault state
+///   3. `[]` System Program
+pub struct Deposit<'a> {
+    /// Vault owner, funding the deposit.
+    pub owner: &'a AccountInfo,
+
+    /// Vault PDA.
+    pub vault: &'a AccountInfo,
+
+    /// Vault state PDA.
+    pub state: &'a AccountInfo,
+
+    /// System Program, used by the vault for the transfer.
+    pub system_program: &'a AccountInfo,
+
+    /// Vault index.
+    pub index: u64,
+
+    /// Amount of lamports to deposit.
+    pub amount: u64,
+}
+
+impl Deposit<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 4] = [
+            AccountMeta::writable_signer(self.owner.key()),
+            AccountMeta::writable(self.vault.key()),
+            AccountMeta::writable(self.state.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        // instruction data
+        // -  [0]     : instruction discriminator (0 = Deposit)
+        // -  [1..9]  : vault index
+        // -  [9..17] : amount
+        let mut instruction_data = [0; 17];
+        instruction_data[0] = *crate::Deposit::DISCRIMINATOR;
+        instruction_data[1..9].copy_from_slice(&self.index.to_le_bytes());
+        instruction_data[9..17].copy_from_slice(&self.amount.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.owner, self.vault, self.state, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Withdraw lamports from a vault back to its owner.
+///
+/// Vaults with an allowlist or a multisig need more accounts than this helper passes; build
+/// the instruction by hand for those.
+///
+/// ### Accounts:
+///   0. `[WRITE, SIGNER]` Owner
+///   1. `[WRITE]` Vault
+///   2. `[WRITE]` Vault state
+///   3. `[]` System Program
+pub struct Withdraw<'a> {
+    /// Vault owner, receiving the lamports.
+    pub owner: &'a AccountInfo,
+
+    /// Vault PDA.
+    pub vault: &'a AccountInfo,
+
+    /// Vault state PDA.
+    pub state: &'a AccountInfo,
+
+    /// System Program, used by the vault for the transfer.
+    pub system_program: &'a AccountInfo,
+
+    /// Vault index.
+    pub index: u64,
+
+    /// Amount of lamports to withdraw, or `None` to withdraw everything.
+    pub amount: Option<u64>,
+}
+
+impl Withdraw<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // account metadata
+        let account_metas: [AccountMeta; 4] = [
+            AccountMeta::writable_signer(self.owner.key()),
+            AccountMeta::writable(self.vault.key()),
+            AccountMeta::writable(self.state.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ];
+
+        // instruction data
+        // -  [0]     : instruction discriminator (1 = Withdraw)
+        // -  [1..9]  : vault index
+        // -  [9..17] : amount (omitted to withdraw everything)
+        let mut instruction_data = [0; 17];
+        instruction_data[0] = *crate::Withdraw::DISCRIMINATOR;
+        instruction_data[1..9].copy_from_slice(&self.index.to_le_bytes());
+        let len = match self.amount {
+            Some(amount) => {
+                instruction_data[9..17].copy_from_slice(&amount.to_le_bytes());
+                17
+            }
+            None => 9,
+        };
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &instruction_data[..len],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.owner, self.vault, self.state, self.system_program],
+            signers,
+        )
+    }
+}
@@ -0,0 +1,210 @@
+use pinocchio::program_error::ProgramError;
+
+// Program-specific errors.
+// They are surfaced to clients as `ProgramError::Custom(code)`, where `code` is the enum discriminant.
+// Codes are part of the program's interface: new variants are appended, existing ones never renumbered.
+//
+// Malformed input that has an exact builtin counterpart (too few accounts, instruction data of the
+// wrong length, unknown discriminator, arithmetic overflow) still uses the builtin `ProgramError`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum VaultError {
+    // The vault has a time lock and its unlock timestamp has not been reached yet.
+    VaultLocked = 0,
+    // The amount exceeds what the vesting schedule has released so far.
+    NotVested = 1,
+    // An account that must sign the transaction didn't.
+    NotSigner = 2,
+    // The vault account is not the PDA derived from the owner and the vault index.
+    InvalidVaultAddress = 3,
+    // The vault account is not owned by the System Program.
+    InvalidVaultOwner = 4,
+    // The state account is not the state PDA of the vault.
+    InvalidStateAddress = 5,
+    // The state account is not a program-owned vault state.
+    InvalidStateAccount = 6,
+    // `Initialize` was called on a vault that already has a state account.
+    StateAlreadyInitialized = 7,
+    // The instruction needs an initialized vault state.
+    StateNotInitialized = 8,
+    // There is nothing to withdraw.
+    VaultEmpty = 9,
+    // The amount must be greater than zero.
+    ZeroAmount = 10,
+    // The amount exceeds the vault balance.
+    InsufficientFunds = 11,
+    // The first deposit into an empty vault must cover its rent-exempt minimum.
+    DepositBelowRentExemption = 12,
+    // A partial withdrawal must leave the vault either empty or rent-exempt.
+    WithdrawLeavesDust = 13,
+    // The vesting schedule is inconsistent.
+    InvalidSchedule = 14,
+    // The signer is not allowed to perform this operation on the vault.
+    Unauthorized = 15,
+    // The token program is neither the Token program nor Token-2022.
+    InvalidTokenProgram = 16,
+    // The mint is not an initialized mint of the token program.
+    InvalidMint = 17,
+    // A token account has the wrong address, mint, authority or program, or is uninitialized.
+    InvalidTokenAccount = 18,
+    // The withdrawal destination can't be the vault itself.
+    InvalidDestination = 19,
+    // The delegate record is not a valid record for this vault and delegate.
+    InvalidDelegateRecord = 20,
+    // The delegation's expiry slot has passed.
+    DelegationExpired = 21,
+    // The amount exceeds the delegate's remaining allowance.
+    AllowanceExceeded = 22,
+    // The amount exceeds the delegation's per-transaction cap.
+    PerTransactionCapExceeded = 23,
+    // The multisig threshold or member list is inconsistent.
+    InvalidMultisig = 24,
+    // Fewer multisig members signed than the vault's threshold requires.
+    NotEnoughSigners = 25,
+    // The vault has a withdrawal delay, so lamports must go through RequestWithdraw / ExecuteWithdraw.
+    WithdrawalDelayActive = 26,
+    // The pending withdrawal account is not a valid pending withdrawal of this vault.
+    InvalidPendingWithdrawal = 27,
+    // The pending withdrawal's delay has not elapsed yet.
+    WithdrawalNotReady = 28,
+    // The withdrawal delay must not be negative.
+    InvalidWithdrawDelay = 29,
+    // The amount exceeds what the vault's rate limit still allows in the current window.
+    RateLimitExceeded = 30,
+    // The rate limit period is unknown.
+    InvalidRateLimit = 31,
+    // The allowlist account is not this vault's allowlist, or the destination is already listed.
+    InvalidAllowlist = 32,
+    // The allowlist has no room for another destination.
+    AllowlistFull = 33,
+    // The destination is not on the vault's allowlist, or its timelock has not passed yet.
+    DestinationNotAllowed = 34,
+    // The guardian configuration is inconsistent, or the vault has no guardians.
+    InvalidGuardians = 35,
+    // The recovery account is not this vault's recovery proposal, or one is already open.
+    InvalidRecovery = 36,
+    // The recovery's challenge period has not elapsed yet.
+    RecoveryNotReady = 37,
+    // The vault's owner was active within the inactivity period, so the heir can't claim yet.
+    OwnerStillActive = 38,
+    // The inactivity period is negative.
+    InvalidInactivityPeriod = 39,
+    // Close was not given every delegate record, pending withdrawal and allowlist of the vault,
+    // or a recovery proposal is still open.
+    VaultHasOpenAccounts = 40,
+    // The vault is frozen by its freeze authority.
+    VaultFrozen = 41,
+    // A token account passed to Close still holds tokens.
+    VaultHasTokens = 42,
+}
+
+impl VaultError {
+    // Human-readable description, used by clients to decode `Custom` error codes.
+    pub const fn message(&self) -> &'static str {
+        match self {
+            Self::VaultLocked => "Vault is time-locked",
+            Self::NotVested => "Amount exceeds the vested balance",
+            Self::NotSigner => "Missing required signature",
+            Self::InvalidVaultAddress => "Vault account is not the expected PDA",
+            Self::InvalidVaultOwner => "Vault account is not owned by the System Program",
+            Self::InvalidStateAddress => "State account is not the vault's state PDA",
+            Self::InvalidStateAccount => "State account is not a valid vault state",
+            Self::StateAlreadyInitialized => "Vault state is already initialized",
+            Self::StateNotInitialized => "Vault state is not initialized",
+            Self::VaultEmpty => "Vault is empty",
+            Self::ZeroAmount => "Amount must be greater than zero",
+            Self::InsufficientFunds => "Amount exceeds the vault balance",
+            Self::DepositBelowRentExemption => "First deposit must cover the rent-exempt minimum",
+            Self::WithdrawLeavesDust => "Withdrawal would leave the vault below rent exemption",
+            Self::InvalidSchedule => "Invalid vesting schedule",
+            Self::Unauthorized => "Signer is not authorized for this vault",
+            Self::InvalidTokenProgram => "Unsupported token program",
+            Self::InvalidMint => "Invalid mint account",
+            Self::InvalidTokenAccount => "Invalid token account",
+            Self::InvalidDestination => "Invalid withdrawal destination",
+            Self::InvalidDelegateRecord => "Invalid delegate record",
+            Self::DelegationExpired => "Delegation has expired",
+            Self::AllowanceExceeded => "Amount exceeds the remaining allowance",
+            Self::PerTransactionCapExceeded => "Amount exceeds the per-transaction cap",
+            Self::InvalidMultisig => "Invalid multisig configuration",
+            Self::NotEnoughSigners => "Not enough multisig members signed",
+            Self::WithdrawalDelayActive => "Vault requires a delayed withdrawal",
+            Self::InvalidPendingWithdrawal => "Invalid pending withdrawal",
+            Self::WithdrawalNotReady => "Pending withdrawal is not ready yet",
+            Self::InvalidWithdrawDelay => "Invalid withdrawal delay",
+            Self::RateLimitExceeded => "Amount exceeds the rate limit for the current window",
+            Self::InvalidRateLimit => "Invalid rate limit",
+            Self::InvalidAllowlist => "Invalid allowlist",
+            Self::AllowlistFull => "Allowlist is full",
+            Self::DestinationNotAllowed => "Destination is not on the allowlist",
+            Self::InvalidGuardians => "Invalid guardian configuration",
+            Self::InvalidRecovery => "Invalid recovery proposal",
+            Self::RecoveryNotReady => "Recovery challenge period has not elapsed",
+            Self::OwnerStillActive => "Owner has been active within the inactivity period",
+            Self::InvalidInactivityPeriod => "Inactivity period must not be negative",
+            Self::VaultHasOpenAccounts => "Vault still has open accounts",
+            Self::VaultFrozen => "Vault is frozen",
+            Self::VaultHasTokens => "Vault token account still holds tokens",
+        }
+    }
+}
+
+impl From<VaultError> for ProgramError {
+    fn from(error: VaultError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+// Maps a `Custom` error code back to its variant.
+impl TryFrom<u32> for VaultError {
+    type Error = ProgramError;
+
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        Ok(match code {
+            0 => Self::VaultLocked,
+            1 => Self::NotVested,
+            2 => Self::NotSigner,
+            3 => Self::InvalidVaultAddress,
+            4 => Self::InvalidVaultOwner,
+            5 => Self::InvalidStateAddress,
+            6 => Self::InvalidStateAccount,
+            7 => Self::StateAlreadyInitialized,
+            8 => Self::StateNotInitialized,
+            9 => Self::VaultEmpty,
+            10 => Self::ZeroAmount,
+            11 => Self::InsufficientFunds,
+            12 => Self::DepositBelowRentExemption,
+            13 => Self::WithdrawLeavesDust,
+            14 => Self::InvalidSchedule,
+            15 => Self::Unauthorized,
+            16 => Self::InvalidTokenProgram,
+            17 => Self::InvalidMint,
+            18 => Self::InvalidTokenAccount,
+            19 => Self::InvalidDestination,
+            20 => Self::InvalidDelegateRecord,
+            21 => Self::DelegationExpired,
+            22 => Self::AllowanceExceeded,
+            23 => Self::PerTransactionCapExceeded,
+            24 => Self::InvalidMultisig,
+            25 => Self::NotEnoughSigners,
+            26 => Self::WithdrawalDelayActive,
+            27 => Self::InvalidPendingWithdrawal,
+            28 => Self::WithdrawalNotReady,
+            29 => Self::InvalidWithdrawDelay,
+            30 => Self::RateLimitExceeded,
+            31 => Self::InvalidRateLimit,
+            32 => Self::InvalidAllowlist,
+            33 => Self::AllowlistFull,
+            34 => Self::DestinationNotAllowed,
+            35 => Self::InvalidGuardians,
+            36 => Self::InvalidRecovery,
+            37 => Self::RecoveryNotReady,
+            38 => Self::OwnerStillActive,
+            39 => Self::InvalidInactivityPeriod,
+            40 => Self::VaultHasOpenAccounts,
+            41 => Self::VaultFrozen,
+            42 => Self::VaultHasTokens,
+            _ => return Err(ProgramError::InvalidArgument),
+        })
+    }
+}
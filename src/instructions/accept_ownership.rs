@@ -0,0 +1,99 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for taking over a vault proposed by TransferOwnership.
+// Requiring the new owner's signature makes sure a vault is never handed to a key nobody controls.
+pub struct AcceptOwnershipAccounts<'a> {
+    pub new_owner: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for AcceptOwnershipAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [new_owner, vault, state]
+        let [new_owner, vault, state] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The proposed owner must sign.
+        if !new_owner.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: The vault must be initialized.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault_address(&index.to_le_bytes(), vault.key())?;
+
+        // Check 3: The signer must be the owner proposed by TransferOwnership.
+        // An all-zero pending owner can't sign, so this also fails when nothing was proposed.
+        if vault_state.pending_owner().ne(new_owner.key()) {
+            return Err(VaultError::Unauthorized.into());
+        }
+
+        // Check 4: Nor can a transfer proposed before a freeze be completed while it lasts.
+        vault_state.check_not_frozen()?;
+
+        Ok(Self { new_owner, state })
+    }
+}
+
+pub struct AcceptOwnershipInstructionData {
+    pub index: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for AcceptOwnershipInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // We expect exactly 8 bytes for the vault index.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data.try_into().unwrap());
+
+        Ok(Self { index })
+    }
+}
+
+pub struct AcceptOwnership<'a> {
+    pub accounts: AcceptOwnershipAccounts<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AcceptOwnership<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = AcceptOwnershipInstructionData::try_from(data)?;
+        let accounts = AcceptOwnershipAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> AcceptOwnership<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // The vault address depends on its creator, not its owner, so the funds stay where they are.
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_owner(self.accounts.new_owner.key());
+        vault_state.set_pending_owner(&[0; 32]);
+        // The inactivity period restarts with the new owner.
+        vault_state.set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::{Allowlist, VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for adding a destination to the vault's withdrawal allowlist.
+// The first addition creates the allowlist, which restricts withdrawals from then on.
+pub struct AddDestinationAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub allowlist: &'a AccountInfo,
+    pub allowlist_bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for AddDestinationAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, allowlist, system_program, ...multisig signers]
+        let [owner, vault, state, allowlist, _, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The owner pays for the allowlist, so it signs even for a multisig vault.
+        if !owner.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 3: Only the owner (or the vault's multisig quorum) can add destinations.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 4: No new destinations while the vault is frozen.
+        vault_state.check_not_frozen()?;
+
+        // Check 5: The allowlist must be the allowlist PDA of this vault.
+        let allowlist_bump = Allowlist::check_address(allowlist, vault.key())?;
+
+        Ok(Self {
+            owner,
+            vault,
+            state,
+            allowlist,
+            allowlist_bumps: [allowlist_bump],
+        })
+    }
+}
+
+pub struct AddDestinationInstructionData {
+    pub index: u64,
+    pub destination: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for AddDestinationInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][destination: Pubkey]
+        if data.len() != size_of::<u64>() + size_of::<Pubkey>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let destination = data[8..40].try_into().unwrap();
+
+        Ok(Self { index, destination })
+    }
+}
+
+pub struct AddDestination<'a> {
+    pub accounts: AddDestinationAccounts<'a>,
+    pub instruction_data: AddDestinationInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AddDestination<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = AddDestinationInstructionData::try_from(data)?;
+        let accounts = AddDestinationAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> AddDestination<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Create the allowlist on the first addition and switch the vault over to it.
+        if self.accounts.allowlist.data_is_empty() {
+            let seeds = [
+                Seed::from(Allowlist::SEED),
+                Seed::from(self.accounts.vault.key().as_ref()),
+                Seed::from(&self.accounts.allowlist_bumps),
+            ];
+            let signers = [Signer::from(&seeds)];
+
+            create_account_with_minimum_balance_signed(
+                self.accounts.allowlist,
+                Allowlist::LEN,
+                &crate::ID,
+                self.accounts.owner,
+                None,
+                &signers,
+            )?;
+
+            let mut data = self.accounts.allowlist.try_borrow_mut_data()?;
+            let allowlist = Allowlist::init(&mut data)?;
+            allowlist.set_bump(self.accounts.allowlist_bumps[0]);
+            allowlist.set_vault(self.accounts.vault.key());
+
+            let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+            vault_state.set_has_allowlist();
+            vault_state.add_open_account();
+        }
+
+        // 2. The owner signed, so it is still around.
+        let now = Clock::get()?.unix_timestamp;
+        VaultState::from_account_info_mut(self.accounts.state)?.set_last_active(now);
+
+        // 3. The destination only receives withdrawals once the timelock has passed,
+        // leaving time to remove it if the addition wasn't legitimate.
+        let active_ts = now
+            .checked_add(Allowlist::ADD_DELAY)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Allowlist::from_account_info_mut(self.accounts.allowlist)?
+            .add(&self.instruction_data.destination, active_ts)
+    }
+}
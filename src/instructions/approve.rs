@@ -0,0 +1,164 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::{DelegateRecord, VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for granting (or updating) a delegate's withdrawal allowance.
+pub struct ApproveAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub delegate: &'a AccountInfo,
+    pub record: &'a AccountInfo,
+    pub record_bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for ApproveAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, delegate, delegate_record, system_program, ...multisig signers]
+        let [owner, vault, state, delegate, record, _, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The owner pays for the record, so it signs even for a multisig vault.
+        if !owner.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 3: Granting an allowance needs the same approval as a withdrawal.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 4: No new allowances while the vault is frozen.
+        vault_state.check_not_frozen()?;
+
+        // Check 5: The record must be the delegate record PDA of this vault and delegate.
+        let record_bump = DelegateRecord::check_address(record, vault.key(), delegate.key())?;
+
+        Ok(Self {
+            owner,
+            vault,
+            state,
+            delegate,
+            record,
+            record_bumps: [record_bump],
+        })
+    }
+}
+
+pub struct ApproveInstructionData {
+    pub index: u64,
+    pub allowance: u64,
+    pub expiry_slot: u64,
+    pub per_tx_cap: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ApproveInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][allowance: u64][expiry_slot: u64][per_tx_cap: u64]
+        if data.len() != size_of::<u64>() * 4 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let allowance = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let expiry_slot = u64::from_le_bytes(data[16..24].try_into().unwrap());
+        let per_tx_cap = u64::from_le_bytes(data[24..32].try_into().unwrap());
+
+        // 3. An empty allowance is a revocation; use `Revoke` for that.
+        if allowance.eq(&0) {
+            return Err(VaultError::ZeroAmount.into());
+        }
+
+        Ok(Self {
+            index,
+            allowance,
+            expiry_slot,
+            per_tx_cap,
+        })
+    }
+}
+
+pub struct Approve<'a> {
+    pub accounts: ApproveAccounts<'a>,
+    pub instruction_data: ApproveInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Approve<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = ApproveInstructionData::try_from(data)?;
+        let accounts = ApproveAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Approve<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Create the record on the first approval. Later approvals overwrite the allowance.
+        if self.accounts.record.data_is_empty() {
+            let seeds = [
+                Seed::from(DelegateRecord::SEED),
+                Seed::from(self.accounts.vault.key().as_ref()),
+                Seed::from(self.accounts.delegate.key().as_ref()),
+                Seed::from(&self.accounts.record_bumps),
+            ];
+            let signers = [Signer::from(&seeds)];
+
+            create_account_with_minimum_balance_signed(
+                self.accounts.record,
+                DelegateRecord::LEN,
+                &crate::ID,
+                self.accounts.owner,
+                None,
+                &signers,
+            )?;
+
+            let mut data = self.accounts.record.try_borrow_mut_data()?;
+            let record = DelegateRecord::init(&mut data)?;
+            record.set_bump(self.accounts.record_bumps[0]);
+            record.set_vault(self.accounts.vault.key());
+            record.set_delegate(self.accounts.delegate.key());
+
+            VaultState::from_account_info_mut(self.accounts.state)?.add_open_account();
+        }
+
+        // 2. Write the allowance.
+        let mut record = DelegateRecord::from_account_info_mut(self.accounts.record)?;
+        record.set_allowance(self.instruction_data.allowance);
+        record.set_expiry_slot(self.instruction_data.expiry_slot);
+        record.set_per_tx_cap(self.instruction_data.per_tx_cap);
+
+        // 3. The owner signed, so it is still around.
+        VaultState::from_account_info_mut(self.accounts.state)?
+            .set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{close_program_account, RecoveryState, VaultState, ZeroCopyAccount};
+
+// Accounts for the owner to reject a recovery proposal during its challenge period.
+// The recovery account is closed and its rent goes back to whoever paid for it.
+pub struct CancelRecoveryAccounts<'a> {
+    pub state: &'a AccountInfo,
+    pub recovery: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for CancelRecoveryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, recovery, payer, ...multisig signers]
+        let [owner, vault, state, recovery, payer, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can cancel a recovery.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The recovery must belong to this vault, and `payer` must be the account that funded it.
+        RecoveryState::from_account_info(recovery)?.check_keys(vault.key(), payer.key())?;
+
+        Ok(Self {
+            state,
+            recovery,
+            payer,
+        })
+    }
+}
+
+pub struct CancelRecoveryInstructionData {
+    pub index: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for CancelRecoveryInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // We expect exactly 8 bytes for the vault index.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data.try_into().unwrap());
+
+        Ok(Self { index })
+    }
+}
+
+pub struct CancelRecovery<'a> {
+    pub accounts: CancelRecoveryAccounts<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CancelRecovery<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = CancelRecoveryInstructionData::try_from(data)?;
+        let accounts = CancelRecoveryAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> CancelRecovery<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &22;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. The owner signed, so it is still around. The proposal no longer holds up Close.
+        {
+            let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+            vault_state.set_last_active(Clock::get()?.unix_timestamp);
+            vault_state.remove_open_account();
+        }
+
+        // 2. Move the recovery's rent back to the payer, then close it.
+        close_program_account(self.accounts.recovery, self.accounts.payer)
+    }
+}
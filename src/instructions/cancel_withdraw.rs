@@ -0,0 +1,102 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{close_program_account, PendingWithdrawal, VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for dropping a queued withdrawal before it is executed.
+// The pending withdrawal is closed and its rent goes back to the owner.
+pub struct CancelWithdrawAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub pending: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for CancelWithdrawAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, pending_withdrawal, ...multisig signers]
+        let [owner, vault, state, pending, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can cancel withdrawals.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The pending withdrawal must belong to this vault.
+        if PendingWithdrawal::from_account_info(pending)?
+            .vault()
+            .ne(vault.key())
+        {
+            return Err(VaultError::InvalidPendingWithdrawal.into());
+        }
+
+        Ok(Self {
+            owner,
+            state,
+            pending,
+        })
+    }
+}
+
+pub struct CancelWithdrawInstructionData {
+    pub index: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for CancelWithdrawInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // We expect exactly 8 bytes for the vault index.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data.try_into().unwrap());
+
+        Ok(Self { index })
+    }
+}
+
+pub struct CancelWithdraw<'a> {
+    pub accounts: CancelWithdrawAccounts<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for CancelWithdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = CancelWithdrawInstructionData::try_from(data)?;
+        let accounts = CancelWithdrawAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> CancelWithdraw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. The owner signed, so it is still around. The withdrawal no longer holds up Close.
+        {
+            let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+            vault_state.set_last_active(Clock::get()?.unix_timestamp);
+            vault_state.remove_open_account();
+        }
+
+        // 2. Move the pending withdrawal's rent back to the owner, then close it.
+        close_program_account(self.accounts.pending, self.accounts.owner)
+    }
+}
@@ -0,0 +1,47 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Withdraw, WithdrawAccounts, WithdrawAuthority, WithdrawInstructionData};
+
+// Withdrawal signed by the vault's heir once the owner has been inactive for the
+// vault's inactivity period. The lamports always go to the heir; every vault-level
+// check of Withdraw (PDA, time lock, vesting, rate limit, allowlist, rent) still applies.
+pub struct Claim<'a> {
+    pub withdraw: Withdraw<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Claim<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // Same instruction data as Withdraw: [index: u64][amount: u64 (optional)].
+        let instruction_data = WithdrawInstructionData::try_from(data)?;
+
+        // We expect: [heir, owner, vault, state, system_program,
+        //             allowlist (if the vault has one)]
+        // The owner doesn't sign; it is only needed to identify the vault.
+        let [heir, owner, vault, state, _, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let accounts = WithdrawAccounts::new(
+            owner,
+            vault,
+            state,
+            heir,
+            WithdrawAuthority::Heir { heir },
+            instruction_data.index,
+            remaining,
+        )?;
+
+        Ok(Self {
+            withdraw: Withdraw::new(accounts, instruction_data)?,
+        })
+    }
+}
+
+impl<'a> Claim<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &26;
+
+    pub fn process(&mut self) -> ProgramResult {
+        self.withdraw.process()
+    }
+}
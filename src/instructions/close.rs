@@ -0,0 +1,240 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    close_program_account,
+    token::{check_token_program, token_account_balance, CloseAccount},
+    Allowlist, DelegateRecord, PendingWithdrawal, VaultError, VaultState, Withdraw,
+    WithdrawAccounts, WithdrawAuthority, WithdrawInstructionData, ZeroCopyAccount,
+};
+
+// Accounts for tearing a vault down: its lamports, its state and every account tied to it
+// go back to the owner.
+pub struct CloseAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    // Delegate records, pending withdrawals and the allowlist of the vault, closed along with it.
+    pub closing: &'a [AccountInfo],
+    // Empty token accounts of the vault, each followed by its token program, closed along with it.
+    pub token_accounts: &'a [AccountInfo],
+    // The accounts after `token_accounts`: the allowlist if the vault has one, then the multisig
+    // signers.
+    pub remaining: &'a [AccountInfo],
+    // Vault creator and little-endian vault index, part of the signer seeds.
+    pub creator: Pubkey,
+    pub index: [u8; 8],
+    pub bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &CloseInstructionData)> for CloseAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], &CloseInstructionData),
+    ) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, system_program, ...accounts to close (`close_count`),
+        //             ...token account and token program pairs (`token_count`),
+        //             allowlist (if the vault has one), ...multisig signers]
+        let [owner, vault, state, _, rest @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let Some((closing, rest)) = rest.split_at_checked(instruction_data.close_count) else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let Some((token_accounts, remaining)) =
+            rest.split_at_checked(instruction_data.token_count * 2)
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        // The vault PDA signs the token account closures, so we need the canonical bump stored in
+        // the vault state.
+        let index = instruction_data.index.to_le_bytes();
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index, vault.key())?;
+        let creator = *vault_state.creator();
+        let bump = vault_state.bump();
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can close the vault.
+        let (_, signers) = vault_state.split_allowlist(remaining)?;
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: A frozen vault can't be torn down, even once empty.
+        vault_state.check_not_frozen()?;
+
+        // Check 4: Every account to close must belong to this vault, and be listed once.
+        for (position, account) in closing.iter().enumerate() {
+            if !belongs_to(account, vault.key())? {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if closing[..position]
+                .iter()
+                .any(|other| other.key().eq(account.key()))
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        // Check 5: Nothing may be left behind, otherwise it would come back to life if the
+        // vault were initialized again. An open recovery proposal must be cancelled first.
+        if vault_state.open_accounts().ne(&(closing.len() as u32)) {
+            return Err(VaultError::VaultHasOpenAccounts.into());
+        }
+
+        // Check 6: Every listed token account must be held by the vault and empty. Tokens left in a
+        // vault token account would go to whoever initializes the vault again, so they have to be
+        // withdrawn first; the emptied accounts are then closed below.
+        // Only the listed accounts are checked: anyone can create a token account for the vault,
+        // so the program keeps no record of them and can't tell when one is left out.
+        for pair in token_accounts.chunks_exact(2) {
+            let (token_account, token_program) = (&pair[0], &pair[1]);
+            check_token_program(token_program)?;
+            if token_account_balance(token_account, token_program.key(), vault.key())?.ne(&0) {
+                return Err(VaultError::VaultHasTokens.into());
+            }
+        }
+
+        Ok(Self {
+            owner,
+            vault,
+            state,
+            closing,
+            token_accounts,
+            remaining,
+            creator,
+            index,
+            bumps: [bump],
+        })
+    }
+}
+
+// Whether `account` is a delegate record, pending withdrawal or allowlist of `vault`.
+// The three layouts have different lengths, which tells them apart.
+fn belongs_to(account: &AccountInfo, vault: &[u8; 32]) -> Result<bool, ProgramError> {
+    let account_vault = match account.data_len() {
+        DelegateRecord::LEN => *DelegateRecord::from_account_info(account)?.vault(),
+        PendingWithdrawal::LEN => *PendingWithdrawal::from_account_info(account)?.vault(),
+        Allowlist::LEN => *Allowlist::from_account_info(account)?.vault(),
+        _ => return Ok(false),
+    };
+
+    Ok(account_vault.eq(vault))
+}
+
+pub struct CloseInstructionData {
+    pub index: u64,
+    // Number of accounts to close that follow the system program.
+    pub close_count: usize,
+    // Number of token account and token program pairs that follow them.
+    pub token_count: usize,
+}
+
+impl<'a> TryFrom<&'a [u8]> for CloseInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // [index: u64][close_count: u8][token_count: u8]
+        if data.len() != size_of::<u64>() + size_of::<u8>() + size_of::<u8>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let close_count = data[8] as usize;
+        let token_count = data[9] as usize;
+
+        Ok(Self {
+            index,
+            close_count,
+            token_count,
+        })
+    }
+}
+
+pub struct Close<'a> {
+    pub accounts: CloseAccounts<'a>,
+    // Drains the vault into the owner's account, or `None` if the vault is already empty.
+    pub withdraw: Option<Withdraw<'a>>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Close<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = CloseInstructionData::try_from(data)?;
+        let accounts = CloseAccounts::try_from((accounts, &instruction_data))?;
+
+        // Draining the vault is a regular "withdraw everything" to the owner, so every rule of
+        // Withdraw (time lock, vesting, withdrawal delay, rate limit, allowlist) still applies.
+        let withdraw = if accounts.vault.lamports().ne(&0) {
+            let withdraw_accounts = WithdrawAccounts::new(
+                accounts.owner,
+                accounts.vault,
+                accounts.state,
+                accounts.owner,
+                WithdrawAuthority::Owner,
+                instruction_data.index,
+                accounts.remaining,
+            )?;
+            let withdraw_data = WithdrawInstructionData {
+                index: instruction_data.index,
+                amount: None,
+            };
+            Some(Withdraw::new(withdraw_accounts, withdraw_data)?)
+        } else {
+            None
+        };
+
+        Ok(Self { accounts, withdraw })
+    }
+}
+
+impl<'a> Close<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &27;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Send every lamport left in the vault to the owner. The vault is a system account,
+        // so once empty it simply stops existing.
+        if let Some(withdraw) = &mut self.withdraw {
+            withdraw.process()?;
+        }
+
+        // 2. Close the vault's delegate records, pending withdrawals and allowlist.
+        for account in self.accounts.closing {
+            close_program_account(account, self.accounts.owner)?;
+        }
+
+        // 3. Close the vault's empty token accounts. The vault PDA is their authority, so it signs.
+        let seeds = [
+            Seed::from(b"vault"),
+            Seed::from(self.accounts.creator.as_ref()),
+            Seed::from(&self.accounts.index),
+            Seed::from(&self.accounts.bumps),
+        ];
+        let signers = [Signer::from(&seeds)];
+        for pair in self.accounts.token_accounts.chunks_exact(2) {
+            CloseAccount {
+                account: &pair[0],
+                destination: self.accounts.owner,
+                authority: self.accounts.vault,
+                token_program: pair[1].key(),
+            }
+            .invoke_signed(&signers)?;
+        }
+
+        // 4. Wipe the state, so it can't be mistaken for a live vault, and close it. Closing hands
+        // the account back to the system program.
+        self.accounts.state.try_borrow_mut_data()?.fill(0);
+        close_program_account(self.accounts.state, self.accounts.owner)
+    }
+}
@@ -0,0 +1,48 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Withdraw, WithdrawAccounts, WithdrawAuthority, WithdrawInstructionData};
+
+// Withdrawal signed by a delegate instead of the owner.
+// The delegate spends the allowance granted by `Approve`; every vault-level check of Withdraw
+// (PDA, time lock, vesting, rent) still applies.
+pub struct DelegatedWithdraw<'a> {
+    pub withdraw: Withdraw<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for DelegatedWithdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // Same instruction data as Withdraw: [index: u64][amount: u64 (optional)].
+        let instruction_data = WithdrawInstructionData::try_from(data)?;
+
+        // We expect: [delegate, owner, vault, state, delegate_record, destination, system_program,
+        //             allowlist (if the vault has one)]
+        // The owner doesn't sign; it is only needed for the vault seeds.
+        let [delegate, owner, vault, state, record, destination, _, remaining @ ..] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let accounts = WithdrawAccounts::new(
+            owner,
+            vault,
+            state,
+            destination,
+            WithdrawAuthority::Delegate { delegate, record },
+            instruction_data.index,
+            remaining,
+        )?;
+
+        Ok(Self {
+            withdraw: Withdraw::new(accounts, instruction_data)?,
+        })
+    }
+}
+
+impl<'a> DelegatedWithdraw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+
+    pub fn process(&mut self) -> ProgramResult {
+        self.withdraw.process()
+    }
+}
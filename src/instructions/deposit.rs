@@ -1,29 +1,36 @@
 use core::mem::size_of;
 use pinocchio::{
-    account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
     ProgramResult,
 };
 
+use crate::{VaultError, VaultState, ZeroCopyAccount};
+
 // In Pinocchio, we don't use macros like `#[derive(Accounts)]` from Anchor.
 // Instead, we define a struct to hold the accounts and implement `TryFrom` to parse and validate them manually.
 // This gives us full control over the number of checks and optimizations (Zero-Copy).
 pub struct DepositAccounts<'a> {
     pub owner: &'a AccountInfo,
     pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for DepositAccounts<'a> {
     type Error = ProgramError;
 
     // This method parses the array of accounts passed by the runtime.
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    // The vault index comes from the instruction data and is needed to re-derive the vault PDA.
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
         // 1. Destructure the accounts array.
         // We expect specific accounts in a specific order.
         // 'owner': The signer paying for the transaction or deposit.
         // 'vault': The PDA account where funds will be deposited.
+        // 'state': The vault's state PDA, which records the deposit.
         // '_': Use `_` to ignore extra accounts if any (like system program).
         // If the number of accounts doesn't match, we return an error.
-        let [owner, vault, _] = accounts else {
+        let [owner, vault, state, _] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
@@ -32,39 +39,38 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
 
         // Check 1: Ensure the owner signed the transaction.
         if !owner.is_signer() {
-            return Err(ProgramError::InvalidAccountOwner);
+            return Err(VaultError::NotSigner.into());
         }
 
         // Check 2: Verification of the Vault's owner.
         // Pinocchio system might need to own the vault, or it should be a PDA of this program.
         // Here it checks if it's owned by `pinocchio_system::ID`. (Adjust based on actual logic intent).
         if !vault.is_owned_by(&pinocchio_system::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
+            return Err(VaultError::InvalidVaultOwner.into());
         }
 
-        // Check 3: Ensure the vault is empty (lamports == 0) for this specific 'Deposit' logic context
-        // (This seems to imply this deposit might be initializing or expecting an empty state,
-        // or just a specific business rule).
-        if vault.lamports().ne(&0) {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        // Check 3: The vault must be initialized; its program-owned state records the deposit.
+        let vault_state = VaultState::from_account_info(state)?;
 
         // Check 4: PDA Validation.
-        // We verify that the 'vault' account is indeed the correct PDA derived from "vault" + owner public key.
+        // We verify that the 'vault' account is indeed the correct PDA derived from "vault" + owner public key + index.
         // This protects against fake vault accounts being passed.
-        let (vault_key, _) = find_program_address(&[b"vault", owner.key().as_ref()], &crate::ID);
-        if vault.key().ne(&vault_key) {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
+        // The canonical bump stored at initialization saves the cost of `find_program_address`.
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
 
         // Return the validated struct
-        Ok(Self { owner, vault })
+        Ok(Self {
+            owner,
+            vault,
+            state,
+        })
     }
 }
 
 // Struct to hold the instruction data (variables passed to the function).
 // In Anchor, this would be the arguments to the function handler.
 pub struct DepositInstructionData {
+    pub index: u64,
     pub amount: u64,
 }
 
@@ -74,22 +80,24 @@ impl<'a> TryFrom<&'a [u8]> for DepositInstructionData {
     // deserializes the raw byte array into the struct.
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
         // 1. Check data length.
-        // We expect exactly 8 bytes for a u64 amount.
-        if data.len() != size_of::<u64>() {
+        // We expect exactly 16 bytes: a u64 vault index followed by a u64 amount.
+        if data.len() != size_of::<u64>() * 2 {
             return Err(ProgramError::InvalidInstructionData);
         }
 
         // 2. Parse the data.
-        // We convert the first 8 bytes into a u64 accumulator.
-        let amount = u64::from_le_bytes(data.try_into().unwrap());
+        // The first 8 bytes select the vault, the next 8 bytes are the amount.
+        let (index, amount) = data.split_at(size_of::<u64>());
+        let index = u64::from_le_bytes(index.try_into().unwrap());
+        let amount = u64::from_le_bytes(amount.try_into().unwrap());
 
         // 3. Logic Checks on Data
         // Ensure the amount is greater than 0.
         if amount.eq(&0) {
-            return Err(ProgramError::InvalidInstructionData);
+            return Err(VaultError::ZeroAmount.into());
         }
 
-        Ok(Self { amount })
+        Ok(Self { index, amount })
     }
 }
 
@@ -104,9 +112,22 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Deposit<'a> {
     type Error = ProgramError;
 
     fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
-        // Parse accounts and data separately using the implementations above.
-        let accounts = DepositAccounts::try_from(accounts)?;
+        // Parse the data first, since the accounts need the vault index to validate the PDA.
         let instruction_data = DepositInstructionData::try_from(data)?;
+        let accounts = DepositAccounts::try_from((accounts, instruction_data.index))?;
+
+        // Cross-checks that need both the accounts and the data.
+        let balance = accounts.vault.lamports();
+        if balance.eq(&0) {
+            // First deposit: the vault is created by this transfer, so it has to be
+            // funded up to the rent-exempt minimum for a zero-data account.
+            if instruction_data.amount < Rent::get()?.minimum_balance(0) {
+                return Err(VaultError::DepositBelowRentExemption.into());
+            }
+        } else if balance.checked_add(instruction_data.amount).is_none() {
+            // Top-up: the new balance must still fit in a u64.
+            return Err(ProgramError::ArithmeticOverflow);
+        }
 
         Ok(Self {
             accounts,
@@ -133,6 +154,15 @@ impl<'a> Deposit<'a> {
         }
         .invoke()?;
 
+        // Record the deposit in the vault state (vesting schedules release from this total).
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        let total_deposited = vault_state
+            .total_deposited()
+            .checked_add(self.instruction_data.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        vault_state.set_total_deposited(total_deposited);
+        vault_state.set_last_active(Clock::get()?.unix_timestamp);
+
         Ok(())
     }
 }
@@ -0,0 +1,132 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    token::TransferChecked,
+    token::{associated_token_address, check_token_program, mint_decimals, token_account_amount},
+    DepositInstructionData, VaultError, VaultState, ZeroCopyAccount,
+};
+
+// Accounts for depositing SPL tokens into a vault.
+// The tokens are held in the associated token account of the vault PDA, so only this
+// program (signing with the vault seeds) can move them out again.
+pub struct DepositTokenAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub owner_token_account: &'a AccountInfo,
+    pub vault_token_account: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub decimals: u8,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for DepositTokenAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, mint, owner_token_account, vault_token_account, token_program]
+        let [owner, vault, state, mint, owner_token_account, vault_token_account, token_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: Ensure the owner signed the transaction.
+        if !owner.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: Only the legacy Token program and Token-2022 are supported.
+        check_token_program(token_program)?;
+
+        // Check 3: The mint must belong to that token program. We also need its decimals for `TransferChecked`.
+        let decimals = mint_decimals(mint, token_program.key())?;
+
+        // Check 4: PDA Validation.
+        // The vault PDA is the authority of the vault token account, so it must be the right one.
+        // Its seeds use the vault's creator, which only the vault state records.
+        VaultState::from_account_info(state)?.check_vault(
+            owner.key(),
+            &index.to_le_bytes(),
+            vault.key(),
+        )?;
+
+        // Check 5: The destination must be the vault's associated token account for this mint.
+        // It has to exist already (clients typically prepend an idempotent ATA creation).
+        if vault_token_account.key().ne(&associated_token_address(
+            vault.key(),
+            mint.key(),
+            token_program.key(),
+        )) {
+            return Err(VaultError::InvalidTokenAccount.into());
+        }
+        token_account_amount(
+            vault_token_account,
+            token_program.key(),
+            mint.key(),
+            vault.key(),
+        )?;
+
+        Ok(Self {
+            owner,
+            state,
+            mint,
+            owner_token_account,
+            vault_token_account,
+            token_program,
+            decimals,
+        })
+    }
+}
+
+// DepositToken uses the same instruction data as Deposit: [index: u64][amount: u64].
+pub struct DepositToken<'a> {
+    pub accounts: DepositTokenAccounts<'a>,
+    pub instruction_data: DepositInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for DepositToken<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = DepositInstructionData::try_from(data)?;
+        let accounts = DepositTokenAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> DepositToken<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. The owner signed the outer transaction, so no PDA signer is needed here.
+        // For transfer-fee mints the vault is credited the amount net of the fee.
+        TransferChecked {
+            from: self.accounts.owner_token_account,
+            mint: self.accounts.mint,
+            to: self.accounts.vault_token_account,
+            authority: self.accounts.owner,
+            token_program: self.accounts.token_program.key(),
+            amount: self.instruction_data.amount,
+            decimals: self.accounts.decimals,
+        }
+        .invoke()?;
+
+        // 2. The owner signed, so it is still around.
+        VaultState::from_account_info_mut(self.accounts.state)?
+            .set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{close_program_account, RecoveryState, VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for completing a recovery once its challenge period is over.
+// Anyone can send it: the guardians approved the proposal and the owner had time to cancel it.
+pub struct ExecuteRecoveryAccounts<'a> {
+    pub state: &'a AccountInfo,
+    pub recovery: &'a AccountInfo,
+    pub payer: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for ExecuteRecoveryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [vault, state, recovery, payer]
+        let [vault, state, recovery, payer] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault_address(&index.to_le_bytes(), vault.key())?;
+
+        // Check 2: The vault must still have guardians.
+        // Removing them with SetGuardians withdraws the trust placed in them, so a proposal they
+        // approved earlier can't be executed anymore, even once its challenge period is over.
+        // The owner can still close it with CancelRecovery.
+        if vault_state.guardian_threshold().eq(&0) {
+            return Err(VaultError::InvalidRecovery.into());
+        }
+
+        // Check 3: The recovery must belong to this vault, and `payer` must be the account that funded it.
+        let recovery_state = RecoveryState::from_account_info(recovery)?;
+        recovery_state.check_keys(vault.key(), payer.key())?;
+
+        // Check 4: The challenge period must be over.
+        if Clock::get()?.unix_timestamp < recovery_state.ready_ts() {
+            return Err(VaultError::RecoveryNotReady.into());
+        }
+
+        Ok(Self {
+            state,
+            recovery,
+            payer,
+        })
+    }
+}
+
+pub struct ExecuteRecoveryInstructionData {
+    pub index: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ExecuteRecoveryInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // We expect exactly 8 bytes for the vault index.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data.try_into().unwrap());
+
+        Ok(Self { index })
+    }
+}
+
+pub struct ExecuteRecovery<'a> {
+    pub accounts: ExecuteRecoveryAccounts<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ExecuteRecovery<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = ExecuteRecoveryInstructionData::try_from(data)?;
+        let accounts = ExecuteRecoveryAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> ExecuteRecovery<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &23;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Hand the vault to the new owner.
+        // The multisig is removed as well: its threshold and members are cleared, otherwise members
+        // who lost their keys could still keep the new owner out. The new owner can set up a
+        // multisig again with SetMultisig. A pending ownership transfer is discarded.
+        {
+            let new_owner = *RecoveryState::from_account_info(self.accounts.recovery)?.new_owner();
+            let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+            vault_state.set_owner(&new_owner);
+            vault_state.set_pending_owner(&[0; 32]);
+            vault_state.set_multisig(0, &[]);
+            vault_state.set_last_active(Clock::get()?.unix_timestamp);
+            vault_state.remove_open_account();
+        }
+
+        // 2. Close the recovery, returning its rent to the payer.
+        close_program_account(self.accounts.recovery, self.accounts.payer)
+    }
+}
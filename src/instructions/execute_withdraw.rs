@@ -0,0 +1,70 @@
+use core::mem::size_of;
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    close_program_account, PendingWithdrawal, VaultState, Withdraw, WithdrawAccounts,
+    WithdrawAuthority, WithdrawInstructionData, ZeroCopyAccount,
+};
+
+// Carries out a withdrawal queued by RequestWithdraw once its delay has elapsed.
+// Anyone can send it: the recipient and amount were fixed and approved when it was requested.
+// Every vault-level check of Withdraw (PDA, time lock, vesting, rent) still applies.
+pub struct ExecuteWithdraw<'a> {
+    pub withdraw: Withdraw<'a>,
+    pub pending: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ExecuteWithdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // We expect exactly 8 bytes for the vault index.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let index = u64::from_le_bytes(data.try_into().unwrap());
+
+        // We expect: [owner, vault, state, pending_withdrawal, destination, system_program,
+        //             allowlist (if the vault has one)]
+        // The owner doesn't sign; it is needed for the vault seeds and receives the pending rent.
+        let [owner, vault, state, pending, destination, _, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let accounts = WithdrawAccounts::new(
+            owner,
+            vault,
+            state,
+            destination,
+            WithdrawAuthority::Pending { pending },
+            index,
+            remaining,
+        )?;
+
+        // The amount comes from the pending withdrawal, not from the instruction.
+        let amount = PendingWithdrawal::from_account_info(pending)?.amount();
+        let instruction_data = WithdrawInstructionData {
+            index,
+            amount: Some(amount),
+        };
+
+        Ok(Self {
+            withdraw: Withdraw::new(accounts, instruction_data)?,
+            pending,
+        })
+    }
+}
+
+impl<'a> ExecuteWithdraw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Pay out the withdrawal.
+        self.withdraw.process()?;
+
+        // 2. Close the pending withdrawal so it can't be executed twice, returning its rent
+        // to the owner who paid for it.
+        VaultState::from_account_info_mut(self.withdraw.accounts.state)?.remove_open_account();
+
+        close_program_account(self.pending, self.withdraw.accounts.owner)
+    }
+}
@@ -0,0 +1,80 @@
+use core::mem::size_of;
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{VaultState, ZeroCopyAccount};
+
+// Accounts for the freeze authority to halt (Freeze) or resume (Thaw) withdrawals and
+// configuration changes. Deposits keep working while the vault is frozen, and so does everything
+// that only takes something away (Revoke, RemoveDestination, CancelWithdraw, CancelRecovery).
+pub struct FreezeAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for FreezeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [freeze_authority, vault, state]
+        let [freeze_authority, vault, state] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized. The owner is not involved: the freeze is
+        // meant for incidents where its key can't be trusted.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault_address(&index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the vault's freeze authority can freeze or thaw it.
+        vault_state.check_freeze_authority(freeze_authority)?;
+
+        Ok(Self { state })
+    }
+}
+
+// Freeze and Thaw both take the vault index alone.
+pub struct FreezeInstructionData {
+    pub index: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for FreezeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // We expect exactly 8 bytes for the vault index.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data.try_into().unwrap());
+
+        Ok(Self { index })
+    }
+}
+
+pub struct Freeze<'a> {
+    pub accounts: FreezeAccounts<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Freeze<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = FreezeInstructionData::try_from(data)?;
+        let accounts = FreezeAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Freeze<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &29;
+
+    pub fn process(&mut self) -> ProgramResult {
+        VaultState::from_account_info_mut(self.accounts.state)?.set_frozen(true);
+
+        Ok(())
+    }
+}
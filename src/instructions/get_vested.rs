@@ -0,0 +1,77 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program::set_return_data,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultError, VaultState, ZeroCopyAccount};
+
+// Read-only instruction reporting how much of a vault has vested.
+// It doesn't modify any account: the result is returned through the transaction's return data,
+// so clients typically call it through `simulateTransaction`.
+pub struct GetVestedAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetVestedAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        // 1. Unpack the accounts
+        // We expect: [viewer, state]
+        let [viewer, state] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Checks
+
+        // Check 1: The viewer must sign, so the answer is only given to the people it concerns.
+        if !viewer.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: The state must be an initialized, program-owned vault state.
+        // Only `Initialize` writes these accounts, so their content can be trusted.
+        let vault_state = VaultState::from_account_info(state)?;
+
+        // Check 3: Only the owner and the designated beneficiary may query the schedule.
+        if viewer.key().ne(vault_state.owner()) && viewer.key().ne(vault_state.beneficiary()) {
+            return Err(VaultError::Unauthorized.into());
+        }
+
+        Ok(Self { state })
+    }
+}
+
+pub struct GetVested<'a> {
+    pub accounts: GetVestedAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetVested<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = GetVestedAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> GetVested<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let vault_state = VaultState::from_account_info(self.accounts.state)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Return data: [vested: u64][withdrawable: u64]
+        let mut return_data = [0u8; 16];
+        return_data[0..8].copy_from_slice(&vault_state.vested_amount(now).to_le_bytes());
+        return_data[8..16].copy_from_slice(&vault_state.withdrawable_amount(now).to_le_bytes());
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultState, ZeroCopyAccount};
+
+// Accounts for the cheapest way for the owner to show it is still around.
+// Every owner-authorized instruction refreshes the activity timestamp; this one does nothing else.
+pub struct HeartbeatAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for HeartbeatAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, ...multisig signers]
+        let [owner, vault, state, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) counts as activity.
+        vault_state.check_authority(owner, signers)?;
+
+        Ok(Self { state })
+    }
+}
+
+pub struct HeartbeatInstructionData {
+    pub index: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for HeartbeatInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // We expect exactly 8 bytes for the vault index.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data.try_into().unwrap());
+
+        Ok(Self { index })
+    }
+}
+
+pub struct Heartbeat<'a> {
+    pub accounts: HeartbeatAccounts<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Heartbeat<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = HeartbeatInstructionData::try_from(data)?;
+        let accounts = HeartbeatAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Heartbeat<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &24;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Restart the inactivity period.
+        VaultState::from_account_info_mut(self.accounts.state)?
+            .set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
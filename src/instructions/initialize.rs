@@ -0,0 +1,191 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::{VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for creating the state account of a vault.
+pub struct InitializeAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub vault_bump: u8,
+    pub state_bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for InitializeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, system_program]
+        let [owner, vault, state, _] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: Ensure the owner signed the transaction (they also pay for the state account).
+        if !owner.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: PDA Validation.
+        // The vault itself does not need to exist yet, but it has to be the owner's vault for this index.
+        // The owner becomes the vault's creator: its address stays the same if ownership moves later.
+        let (vault_key, vault_bump) = find_program_address(
+            &[b"vault", owner.key().as_ref(), &index.to_le_bytes()],
+            &crate::ID,
+        );
+        if vault.key().ne(&vault_key) {
+            return Err(VaultError::InvalidVaultAddress.into());
+        }
+
+        // Check 3: The state account must be the vault's state PDA.
+        let state_bump = VaultState::check_address(state, vault.key())?;
+
+        // Check 4: A vault can only be initialized once, so its configuration can't be rewritten later.
+        if !state.is_owned_by(&pinocchio_system::ID) || !state.data_is_empty() {
+            return Err(VaultError::StateAlreadyInitialized.into());
+        }
+
+        Ok(Self {
+            owner,
+            vault,
+            state,
+            vault_bump,
+            state_bumps: [state_bump],
+        })
+    }
+}
+
+pub struct InitializeInstructionData {
+    pub index: u64,
+    pub unlock_ts: i64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub beneficiary: Pubkey,
+    pub label: [u8; 32],
+}
+
+impl InitializeInstructionData {
+    // [index: u64][unlock_ts: i64][start_ts: i64][cliff_ts: i64][end_ts: i64][beneficiary: Pubkey][label: [u8; 32]]
+    pub const LEN: usize = size_of::<u64>() + size_of::<i64>() * 4 + size_of::<Pubkey>() + 32;
+}
+
+impl<'a> TryFrom<&'a [u8]> for InitializeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let unlock_ts = i64::from_le_bytes(data[8..16].try_into().unwrap());
+        let start_ts = i64::from_le_bytes(data[16..24].try_into().unwrap());
+        let cliff_ts = i64::from_le_bytes(data[24..32].try_into().unwrap());
+        let end_ts = i64::from_le_bytes(data[32..40].try_into().unwrap());
+        let beneficiary: Pubkey = data[40..72].try_into().unwrap();
+        let label: [u8; 32] = data[72..104].try_into().unwrap();
+
+        // 3. Validate the vesting schedule.
+        // `end_ts == 0` disables vesting, in which case the other schedule fields must be unset too.
+        if end_ts.eq(&0) {
+            if start_ts.ne(&0) || cliff_ts.ne(&0) {
+                return Err(VaultError::InvalidSchedule.into());
+            }
+        } else if start_ts >= end_ts
+            || (cliff_ts.ne(&0) && (cliff_ts < start_ts || cliff_ts > end_ts))
+        {
+            return Err(VaultError::InvalidSchedule.into());
+        }
+
+        Ok(Self {
+            index,
+            unlock_ts,
+            start_ts,
+            cliff_ts,
+            end_ts,
+            beneficiary,
+            label,
+        })
+    }
+}
+
+pub struct Initialize<'a> {
+    pub accounts: InitializeAccounts<'a>,
+    pub instruction_data: InitializeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Initialize<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = InitializeInstructionData::try_from(data)?;
+        let accounts = InitializeAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> Initialize<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Create the state account, owned by this program.
+        // The state PDA signs its own creation.
+        let seeds = [
+            Seed::from(VaultState::SEED),
+            Seed::from(self.accounts.vault.key().as_ref()),
+            Seed::from(&self.accounts.state_bumps),
+        ];
+        let signers = [Signer::from(&seeds)];
+
+        create_account_with_minimum_balance_signed(
+            self.accounts.state,
+            VaultState::LEN,
+            &crate::ID,
+            self.accounts.owner,
+            None,
+            &signers,
+        )?;
+
+        // 2. Write the configuration and metadata.
+        let mut data = self.accounts.state.try_borrow_mut_data()?;
+        let state = VaultState::init(&mut data)?;
+        state.set_bump(self.accounts.vault_bump);
+        state.set_creator(self.accounts.owner.key());
+        state.set_owner(self.accounts.owner.key());
+        state.set_index(self.instruction_data.index);
+        let clock = Clock::get()?;
+        state.set_created_slot(clock.slot);
+        state.set_last_active(clock.unix_timestamp);
+        state.set_label(&self.instruction_data.label);
+        state.set_beneficiary(&self.instruction_data.beneficiary);
+        state.set_unlock_ts(self.instruction_data.unlock_ts);
+        state.set_schedule(
+            self.instruction_data.start_ts,
+            self.instruction_data.cliff_ts,
+            self.instruction_data.end_ts,
+        );
+        // Anything already sitting in the vault counts as deposited, otherwise the schedule
+        // would never release it.
+        state.set_total_deposited(self.accounts.vault.lamports());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    ProgramResult,
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::VaultError;
+
+// Accounts for draining a vault created before vaults had a state account.
+// Those vaults live at `["vault", owner]` and have no configuration, so the owner's signature is
+// all that guards them, as it was when they were created.
+pub struct LegacyWithdrawAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for LegacyWithdrawAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        // 1. Unpack the accounts
+        // We expect the original Withdraw accounts: [owner, vault, system_program]
+        let [owner, vault, _] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Checks
+
+        // Check 1: Ensure the owner is a signer.
+        // The lamports only ever go back to the account that signed the transaction.
+        if !owner.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: The vault is a PDA holding lamports, owned by the system program.
+        if !vault.is_owned_by(&pinocchio_system::ID) {
+            return Err(VaultError::InvalidVaultOwner.into());
+        }
+
+        // Check 3: Nothing to withdraw from an empty vault.
+        if vault.lamports().eq(&0) {
+            return Err(VaultError::VaultEmpty.into());
+        }
+
+        // Check 4: PDA Validation
+        // There is no state account storing the bump, so we search for it.
+        // Seeds: "vault" + owner_pubkey
+        let (vault_key, bump) = find_program_address(&[b"vault", owner.key().as_ref()], &crate::ID);
+        if vault_key.ne(vault.key()) {
+            return Err(VaultError::InvalidVaultAddress.into());
+        }
+
+        Ok(Self {
+            owner,
+            vault,
+            bumps: [bump],
+        })
+    }
+}
+
+// The original Withdraw instruction, kept so that lamports deposited into `["vault", owner]`
+// vaults before the index and state account were introduced can still be recovered. It shares
+// Withdraw's discriminator and is selected by the original, empty instruction data; every current
+// Withdraw carries at least the vault index.
+pub struct LegacyWithdraw<'a> {
+    pub accounts: LegacyWithdrawAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for LegacyWithdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = LegacyWithdrawAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> LegacyWithdraw<'a> {
+    // Execution logic
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Prepare PDA Signers
+        let seeds = [
+            Seed::from(b"vault"),
+            Seed::from(self.accounts.owner.key().as_ref()),
+            Seed::from(&self.accounts.bumps),
+        ];
+        let signers = [Signer::from(&seeds)];
+
+        // 2. Send everything back to the owner. Once empty, the vault simply stops existing.
+        Transfer {
+            from: self.accounts.vault,
+            to: self.accounts.owner,
+            lamports: self.accounts.vault.lamports(),
+        }
+        .invoke_signed(&signers)
+    }
+}
@@ -1,5 +1,65 @@
+pub mod accept_ownership;
+pub mod add_destination;
+pub mod approve;
+pub mod cancel_recovery;
+pub mod cancel_withdraw;
+pub mod claim;
+pub mod close;
+pub mod delegated_withdraw;
 pub mod deposit;
+pub mod deposit_token;
+pub mod execute_recovery;
+pub mod execute_withdraw;
+pub mod freeze;
+pub mod get_vested;
+pub mod heartbeat;
+pub mod initialize;
+pub mod legacy_withdraw;
+pub mod propose_recovery;
+pub mod remove_destination;
+pub mod request_withdraw;
+pub mod revoke;
+pub mod set_freeze_authority;
+pub mod set_guardians;
+pub mod set_inheritance;
+pub mod set_multisig;
+pub mod set_rate_limit;
+pub mod set_withdraw_delay;
+pub mod thaw;
+pub mod transfer_ownership;
 pub mod withdraw;
+pub mod withdraw_to;
+pub mod withdraw_token;
 
+pub use accept_ownership::*;
+pub use add_destination::*;
+pub use approve::*;
+pub use cancel_recovery::*;
+pub use cancel_withdraw::*;
+pub use claim::*;
+pub use close::*;
+pub use delegated_withdraw::*;
 pub use deposit::*;
+pub use deposit_token::*;
+pub use execute_recovery::*;
+pub use execute_withdraw::*;
+pub use freeze::*;
+pub use get_vested::*;
+pub use heartbeat::*;
+pub use initialize::*;
+pub use legacy_withdraw::*;
+pub use propose_recovery::*;
+pub use remove_destination::*;
+pub use request_withdraw::*;
+pub use revoke::*;
+pub use set_freeze_authority::*;
+pub use set_guardians::*;
+pub use set_inheritance::*;
+pub use set_multisig::*;
+pub use set_rate_limit::*;
+pub use set_withdraw_delay::*;
+pub use thaw::*;
+pub use transfer_ownership::*;
 pub use withdraw::*;
+pub use withdraw_to::*;
+pub use withdraw_token::*;
@@ -0,0 +1,154 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::{RecoveryState, VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for a guardian proposal to reassign the vault to a new owner.
+pub struct ProposeRecoveryAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub recovery: &'a AccountInfo,
+    // Unix timestamp from which the recovery can be executed.
+    pub ready_ts: i64,
+    pub recovery_bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for ProposeRecoveryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [payer, vault, state, recovery, system_program, ...guardian signers]
+        let [payer, vault, state, recovery, _, guardians @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The payer funds the recovery account.
+        if !payer.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: The vault must be initialized. The owner is not involved: it may have lost its key.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault_address(&index.to_le_bytes(), vault.key())?;
+
+        // Check 3: Enough guardians must sign the proposal.
+        vault_state.check_guardian_quorum(guardians)?;
+
+        // Check 4: The recovery must be the vault's recovery PDA, and no other proposal may be open.
+        let recovery_bump = RecoveryState::check_address(recovery, vault.key())?;
+        if !recovery.data_is_empty() {
+            return Err(VaultError::InvalidRecovery.into());
+        }
+
+        // The owner can cancel the proposal until the challenge period is over.
+        let ready_ts = Clock::get()?
+            .unix_timestamp
+            .checked_add(vault_state.challenge_period())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(Self {
+            payer,
+            vault,
+            state,
+            recovery,
+            ready_ts,
+            recovery_bumps: [recovery_bump],
+        })
+    }
+}
+
+pub struct ProposeRecoveryInstructionData {
+    pub index: u64,
+    pub new_owner: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for ProposeRecoveryInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][new_owner: Pubkey]
+        if data.len() != size_of::<u64>() + size_of::<Pubkey>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let new_owner: Pubkey = data[8..40].try_into().unwrap();
+
+        // 3. Nobody can sign for the all-zero key, so the vault would be lost for good.
+        if new_owner.eq(&[0; 32]) {
+            return Err(VaultError::InvalidRecovery.into());
+        }
+
+        Ok(Self { index, new_owner })
+    }
+}
+
+pub struct ProposeRecovery<'a> {
+    pub accounts: ProposeRecoveryAccounts<'a>,
+    pub instruction_data: ProposeRecoveryInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for ProposeRecovery<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = ProposeRecoveryInstructionData::try_from(data)?;
+        let accounts = ProposeRecoveryAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> ProposeRecovery<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &21;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Create the recovery account, paid for by the payer.
+        let seeds = [
+            Seed::from(RecoveryState::SEED),
+            Seed::from(self.accounts.vault.key().as_ref()),
+            Seed::from(&self.accounts.recovery_bumps),
+        ];
+        let signers = [Signer::from(&seeds)];
+
+        create_account_with_minimum_balance_signed(
+            self.accounts.recovery,
+            RecoveryState::LEN,
+            &crate::ID,
+            self.accounts.payer,
+            None,
+            &signers,
+        )?;
+
+        // 2. Record who takes over, and when.
+        let mut data = self.accounts.recovery.try_borrow_mut_data()?;
+        let recovery = RecoveryState::init(&mut data)?;
+        recovery.set_bump(self.accounts.recovery_bumps[0]);
+        recovery.set_vault(self.accounts.vault.key());
+        recovery.set_new_owner(&self.instruction_data.new_owner);
+        recovery.set_payer(self.accounts.payer.key());
+        recovery.set_ready_ts(self.accounts.ready_ts);
+
+        // 3. The vault can't be closed while the proposal is open.
+        VaultState::from_account_info_mut(self.accounts.state)?.add_open_account();
+
+        Ok(())
+    }
+}
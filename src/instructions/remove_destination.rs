@@ -0,0 +1,98 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{Allowlist, VaultState, ZeroCopyAccount};
+
+// Accounts for removing a destination from the vault's withdrawal allowlist.
+// Unlike additions, removals apply immediately.
+pub struct RemoveDestinationAccounts<'a> {
+    pub state: &'a AccountInfo,
+    pub allowlist: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for RemoveDestinationAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, allowlist, ...multisig signers]
+        let [owner, vault, state, allowlist, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can remove destinations.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The allowlist must belong to this vault.
+        Allowlist::from_account_info(allowlist)?.check_vault(vault.key())?;
+
+        Ok(Self { state, allowlist })
+    }
+}
+
+pub struct RemoveDestinationInstructionData {
+    pub index: u64,
+    pub destination: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for RemoveDestinationInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][destination: Pubkey]
+        if data.len() != size_of::<u64>() + size_of::<Pubkey>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let destination = data[8..40].try_into().unwrap();
+
+        Ok(Self { index, destination })
+    }
+}
+
+pub struct RemoveDestination<'a> {
+    pub accounts: RemoveDestinationAccounts<'a>,
+    pub instruction_data: RemoveDestinationInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for RemoveDestination<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = RemoveDestinationInstructionData::try_from(data)?;
+        let accounts = RemoveDestinationAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> RemoveDestination<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // The owner signed, so it is still around.
+        VaultState::from_account_info_mut(self.accounts.state)?
+            .set_last_active(Clock::get()?.unix_timestamp);
+
+        Allowlist::from_account_info_mut(self.accounts.allowlist)?
+            .remove(&self.instruction_data.destination)
+    }
+}
@@ -0,0 +1,187 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::create_account_with_minimum_balance_signed;
+
+use crate::{PendingWithdrawal, VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for queueing a withdrawal behind the vault's withdrawal delay.
+pub struct RequestWithdrawAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub pending: &'a AccountInfo,
+    // Unix timestamp from which the withdrawal can be executed.
+    pub ready_ts: i64,
+    pub pending_bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], &RequestWithdrawInstructionData)>
+    for RequestWithdrawAccounts<'a>
+{
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], &RequestWithdrawInstructionData),
+    ) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, destination, pending_withdrawal, system_program, ...multisig signers]
+        let [owner, vault, state, destination, pending, _, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The owner pays for the pending withdrawal, so it signs even for a multisig vault.
+        if !owner.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        // Check 2: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(
+            owner.key(),
+            &instruction_data.index.to_le_bytes(),
+            vault.key(),
+        )?;
+
+        // Check 3: Requesting a withdrawal needs the same approval as an immediate one.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 4: Nothing can be queued while the vault is frozen.
+        vault_state.check_not_frozen()?;
+
+        // Check 5: Paying the vault back into itself would only inflate the withdrawal counter.
+        if destination.key().eq(vault.key()) {
+            return Err(VaultError::InvalidDestination.into());
+        }
+
+        // Check 6: The pending withdrawal must be the fresh PDA of this vault and request id.
+        let pending_bump = PendingWithdrawal::check_address(
+            pending,
+            vault.key(),
+            &instruction_data.request_id.to_le_bytes(),
+        )?;
+        if !pending.data_is_empty() {
+            return Err(VaultError::InvalidPendingWithdrawal.into());
+        }
+
+        // The delay is fixed when the withdrawal is requested.
+        let now = Clock::get()?.unix_timestamp;
+        let ready_ts = now
+            .checked_add(vault_state.withdraw_delay(now))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(Self {
+            owner,
+            vault,
+            state,
+            destination,
+            pending,
+            ready_ts,
+            pending_bumps: [pending_bump],
+        })
+    }
+}
+
+pub struct RequestWithdrawInstructionData {
+    pub index: u64,
+    // Caller-chosen id, so a vault can have several withdrawals queued at once.
+    pub request_id: u64,
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for RequestWithdrawInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][request_id: u64][amount: u64]
+        if data.len() != size_of::<u64>() * 3 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let request_id = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let amount = u64::from_le_bytes(data[16..24].try_into().unwrap());
+
+        // 3. The amount is fixed at request time, so "withdraw everything" isn't supported here.
+        if amount.eq(&0) {
+            return Err(VaultError::ZeroAmount.into());
+        }
+
+        Ok(Self {
+            index,
+            request_id,
+            amount,
+        })
+    }
+}
+
+pub struct RequestWithdraw<'a> {
+    pub accounts: RequestWithdrawAccounts<'a>,
+    pub instruction_data: RequestWithdrawInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for RequestWithdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = RequestWithdrawInstructionData::try_from(data)?;
+        let accounts = RequestWithdrawAccounts::try_from((accounts, &instruction_data))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> RequestWithdraw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. Create the pending withdrawal, paid for by the owner.
+        let request_id = self.instruction_data.request_id.to_le_bytes();
+        let seeds = [
+            Seed::from(PendingWithdrawal::SEED),
+            Seed::from(self.accounts.vault.key().as_ref()),
+            Seed::from(&request_id),
+            Seed::from(&self.accounts.pending_bumps),
+        ];
+        let signers = [Signer::from(&seeds)];
+
+        create_account_with_minimum_balance_signed(
+            self.accounts.pending,
+            PendingWithdrawal::LEN,
+            &crate::ID,
+            self.accounts.owner,
+            None,
+            &signers,
+        )?;
+
+        // 2. Record what ExecuteWithdraw will pay out, and when.
+        let mut data = self.accounts.pending.try_borrow_mut_data()?;
+        let pending = PendingWithdrawal::init(&mut data)?;
+        pending.set_bump(self.accounts.pending_bumps[0]);
+        pending.set_vault(self.accounts.vault.key());
+        pending.set_destination(self.accounts.destination.key());
+        pending.set_amount(self.instruction_data.amount);
+        pending.set_ready_ts(self.accounts.ready_ts);
+
+        // 3. The owner signed, so it is still around. The vault can't be closed while the
+        // withdrawal is pending.
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_last_active(Clock::get()?.unix_timestamp);
+        vault_state.add_open_account();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{close_program_account, DelegateRecord, VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for revoking a delegate's allowance.
+// The record is closed and its rent goes back to the owner.
+pub struct RevokeAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub record: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for RevokeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, delegate_record, ...multisig signers]
+        let [owner, vault, state, record, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can revoke allowances.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The record must be a delegate record of this vault.
+        if DelegateRecord::from_account_info(record)?
+            .vault()
+            .ne(vault.key())
+        {
+            return Err(VaultError::InvalidDelegateRecord.into());
+        }
+
+        Ok(Self {
+            owner,
+            state,
+            record,
+        })
+    }
+}
+
+pub struct RevokeInstructionData {
+    pub index: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for RevokeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // We expect exactly 8 bytes for the vault index.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let index = u64::from_le_bytes(data.try_into().unwrap());
+
+        Ok(Self { index })
+    }
+}
+
+pub struct Revoke<'a> {
+    pub accounts: RevokeAccounts<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Revoke<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = RevokeInstructionData::try_from(data)?;
+        let accounts = RevokeAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Revoke<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // 1. The owner signed, so it is still around. The record no longer holds up Close.
+        {
+            let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+            vault_state.set_last_active(Clock::get()?.unix_timestamp);
+            vault_state.remove_open_account();
+        }
+
+        // 2. Move the record's rent back to the owner, then close it.
+        close_program_account(self.accounts.record, self.accounts.owner)
+    }
+}
@@ -0,0 +1,99 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultState, ZeroCopyAccount};
+
+// Accounts for choosing the key able to freeze the vault.
+pub struct SetFreezeAuthorityAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for SetFreezeAuthorityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, ...multisig signers]
+        let [owner, vault, state, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can choose the freeze authority.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: A compromised owner key must not be able to swap the authority out to escape a freeze.
+        vault_state.check_not_frozen()?;
+
+        Ok(Self { state })
+    }
+}
+
+pub struct SetFreezeAuthorityInstructionData {
+    pub index: u64,
+    pub freeze_authority: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetFreezeAuthorityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][freeze_authority: Pubkey]
+        if data.len() != size_of::<u64>() + size_of::<Pubkey>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let freeze_authority: Pubkey = data[8..40].try_into().unwrap();
+
+        Ok(Self {
+            index,
+            freeze_authority,
+        })
+    }
+}
+
+pub struct SetFreezeAuthority<'a> {
+    pub accounts: SetFreezeAuthorityAccounts<'a>,
+    pub instruction_data: SetFreezeAuthorityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetFreezeAuthority<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = SetFreezeAuthorityInstructionData::try_from(data)?;
+        let accounts = SetFreezeAuthorityAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetFreezeAuthority<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &28;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // All zeroes removes the freeze authority.
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_freeze_authority(&self.instruction_data.freeze_authority);
+        vault_state.set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
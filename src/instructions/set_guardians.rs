@@ -0,0 +1,126 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultState, ZeroCopyAccount};
+
+// Accounts for registering the guardians able to recover the vault.
+pub struct SetGuardiansAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for SetGuardiansAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, ...multisig signers]
+        let [owner, vault, state, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can choose its guardians.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The guardians can't be changed while the vault is frozen.
+        vault_state.check_not_frozen()?;
+
+        Ok(Self { state })
+    }
+}
+
+pub struct SetGuardiansInstructionData {
+    pub index: u64,
+    pub threshold: u8,
+    pub challenge_period: i64,
+    pub guardian_count: usize,
+    pub guardians: [Pubkey; VaultState::MAX_GUARDIANS],
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetGuardiansInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][threshold: u8][challenge_period: i64][guardians: 32 bytes each, up to MAX_GUARDIANS]
+        const HEADER: usize = size_of::<u64>() + size_of::<u8>() + size_of::<i64>();
+        let Some(guardians_data) = data.get(HEADER..) else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        if guardians_data.len() % size_of::<Pubkey>() != 0
+            || guardians_data.len() / size_of::<Pubkey>() > VaultState::MAX_GUARDIANS
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let threshold = data[8];
+        let challenge_period = i64::from_le_bytes(data[9..17].try_into().unwrap());
+        let mut guardians = [[0; 32]; VaultState::MAX_GUARDIANS];
+        let mut guardian_count = 0;
+        for guardian in guardians_data.chunks_exact(size_of::<Pubkey>()) {
+            guardians[guardian_count] = guardian.try_into().unwrap();
+            guardian_count += 1;
+        }
+
+        // 3. The threshold must be reachable, the guardians distinct and the period positive.
+        // A zero threshold with no guardians removes them.
+        VaultState::check_guardians(threshold, &guardians[..guardian_count], challenge_period)?;
+
+        Ok(Self {
+            index,
+            threshold,
+            challenge_period,
+            guardian_count,
+            guardians,
+        })
+    }
+}
+
+pub struct SetGuardians<'a> {
+    pub accounts: SetGuardiansAccounts<'a>,
+    pub instruction_data: SetGuardiansInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetGuardians<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = SetGuardiansInstructionData::try_from(data)?;
+        let accounts = SetGuardiansAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetGuardians<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = &self.instruction_data;
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_guardians(
+            data.threshold,
+            &data.guardians[..data.guardian_count],
+            data.challenge_period,
+        );
+        vault_state.set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for configuring the dead-man switch: who may claim the vault, and after how long
+// without owner activity.
+pub struct SetInheritanceAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for SetInheritanceAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, ...multisig signers]
+        let [owner, vault, state, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can choose its heir.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The heir can't be changed while the vault is frozen.
+        vault_state.check_not_frozen()?;
+
+        Ok(Self { state })
+    }
+}
+
+pub struct SetInheritanceInstructionData {
+    pub index: u64,
+    pub heir: Pubkey,
+    pub inactivity_period: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetInheritanceInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][heir: Pubkey][inactivity_period: i64]
+        if data.len() != size_of::<u64>() + size_of::<Pubkey>() + size_of::<i64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let heir: Pubkey = data[8..40].try_into().unwrap();
+        let inactivity_period = i64::from_le_bytes(data[40..48].try_into().unwrap());
+
+        // 3. The period is a number of seconds; zero turns the switch off.
+        if inactivity_period < 0 {
+            return Err(VaultError::InvalidInactivityPeriod.into());
+        }
+
+        Ok(Self {
+            index,
+            heir,
+            inactivity_period,
+        })
+    }
+}
+
+pub struct SetInheritance<'a> {
+    pub accounts: SetInheritanceAccounts<'a>,
+    pub instruction_data: SetInheritanceInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetInheritance<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = SetInheritanceInstructionData::try_from(data)?;
+        let accounts = SetInheritanceAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetInheritance<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &25;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Only the heir changes: the beneficiary set at Initialize keeps access to the vesting
+        // schedule (see GetVested).
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_heir(&self.instruction_data.heir);
+        vault_state.set_inactivity_period(self.instruction_data.inactivity_period);
+        vault_state.set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
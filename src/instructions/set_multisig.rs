@@ -0,0 +1,120 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultState, ZeroCopyAccount};
+
+// Accounts for replacing the multisig configuration of a vault.
+pub struct SetMultisigAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for SetMultisigAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, ...multisig signers]
+        let [owner, vault, state, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Changes are approved by the *current* authority: the owner alone for a vault
+        // without a multisig, the current quorum otherwise.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The vault's signers can't be changed while it is frozen.
+        vault_state.check_not_frozen()?;
+
+        Ok(Self { state })
+    }
+}
+
+pub struct SetMultisigInstructionData {
+    pub index: u64,
+    pub threshold: u8,
+    pub member_count: usize,
+    pub members: [Pubkey; VaultState::MAX_MEMBERS],
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetMultisigInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][threshold: u8][members: 32 bytes each, up to MAX_MEMBERS]
+        const HEADER: usize = size_of::<u64>() + size_of::<u8>();
+        let Some(members_data) = data.get(HEADER..) else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+        if members_data.len() % size_of::<Pubkey>() != 0
+            || members_data.len() / size_of::<Pubkey>() > VaultState::MAX_MEMBERS
+        {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let threshold = data[8];
+        let mut members = [[0; 32]; VaultState::MAX_MEMBERS];
+        let mut member_count = 0;
+        for member in members_data.chunks_exact(size_of::<Pubkey>()) {
+            members[member_count] = member.try_into().unwrap();
+            member_count += 1;
+        }
+
+        // 3. The threshold must be reachable and the members distinct.
+        // A zero threshold with no members removes the multisig.
+        VaultState::check_multisig(threshold, &members[..member_count])?;
+
+        Ok(Self {
+            index,
+            threshold,
+            member_count,
+            members,
+        })
+    }
+}
+
+pub struct SetMultisig<'a> {
+    pub accounts: SetMultisigAccounts<'a>,
+    pub instruction_data: SetMultisigInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetMultisig<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = SetMultisigInstructionData::try_from(data)?;
+        let accounts = SetMultisigAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetMultisig<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let data = &self.instruction_data;
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_multisig(data.threshold, &data.members[..data.member_count]);
+        vault_state.set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for changing how many lamports may leave the vault per day or per epoch.
+pub struct SetRateLimitAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for SetRateLimitAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, ...multisig signers]
+        let [owner, vault, state, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can change the limit.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The limit can't be changed while the vault is frozen.
+        vault_state.check_not_frozen()?;
+
+        Ok(Self { state })
+    }
+}
+
+pub struct SetRateLimitInstructionData {
+    pub index: u64,
+    pub limit: u64,
+    pub period: u8,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetRateLimitInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][limit: u64][period: u8]
+        if data.len() != size_of::<u64>() * 2 + size_of::<u8>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let limit = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let period = data[16];
+
+        // 3. Only days and epochs are supported.
+        if period.ne(&VaultState::PERIOD_DAY) && period.ne(&VaultState::PERIOD_EPOCH) {
+            return Err(VaultError::InvalidRateLimit.into());
+        }
+
+        Ok(Self {
+            index,
+            limit,
+            period,
+        })
+    }
+}
+
+pub struct SetRateLimit<'a> {
+    pub accounts: SetRateLimitAccounts<'a>,
+    pub instruction_data: SetRateLimitInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetRateLimit<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = SetRateLimitInstructionData::try_from(data)?;
+        let accounts = SetRateLimitAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetRateLimit<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // A stricter limit applies right away; a looser one from the next window on.
+        let clock = Clock::get()?;
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_rate_limit(
+            self.instruction_data.limit,
+            self.instruction_data.period,
+            &clock,
+        );
+        vault_state.set_last_active(clock.unix_timestamp);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultError, VaultState, ZeroCopyAccount};
+
+// Accounts for changing the delay between RequestWithdraw and ExecuteWithdraw.
+pub struct SetWithdrawDelayAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for SetWithdrawDelayAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, ...multisig signers]
+        let [owner, vault, state, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can change the delay.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: The delay can't be changed while the vault is frozen.
+        vault_state.check_not_frozen()?;
+
+        Ok(Self { state })
+    }
+}
+
+pub struct SetWithdrawDelayInstructionData {
+    pub index: u64,
+    pub delay: i64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetWithdrawDelayInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][delay: i64]
+        if data.len() != size_of::<u64>() + size_of::<i64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let delay = i64::from_le_bytes(data[8..16].try_into().unwrap());
+
+        // 3. The delay is a number of seconds.
+        if delay < 0 {
+            return Err(VaultError::InvalidWithdrawDelay.into());
+        }
+
+        Ok(Self { index, delay })
+    }
+}
+
+pub struct SetWithdrawDelay<'a> {
+    pub accounts: SetWithdrawDelayAccounts<'a>,
+    pub instruction_data: SetWithdrawDelayInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetWithdrawDelay<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = SetWithdrawDelayInstructionData::try_from(data)?;
+        let accounts = SetWithdrawDelayAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetWithdrawDelay<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // A longer delay applies right away; a shorter one only after the current delay.
+        let now = Clock::get()?.unix_timestamp;
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_withdraw_delay(self.instruction_data.delay, now);
+        vault_state.set_last_active(now);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{FreezeAccounts, FreezeInstructionData, VaultState, ZeroCopyAccount};
+
+// Lifts a freeze. Same accounts and instruction data as Freeze.
+pub struct Thaw<'a> {
+    pub accounts: FreezeAccounts<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Thaw<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = FreezeInstructionData::try_from(data)?;
+        let accounts = FreezeAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Thaw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &30;
+
+    pub fn process(&mut self) -> ProgramResult {
+        VaultState::from_account_info_mut(self.accounts.state)?.set_frozen(false);
+
+        Ok(())
+    }
+}
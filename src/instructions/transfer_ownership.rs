@@ -0,0 +1,97 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{VaultState, ZeroCopyAccount};
+
+// Accounts for proposing a new owner for the vault.
+// Nothing changes hands until the proposed owner accepts with AcceptOwnership.
+pub struct TransferOwnershipAccounts<'a> {
+    pub state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for TransferOwnershipAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Destructure the accounts array.
+        // We expect: [owner, vault, state, ...multisig signers]
+        let [owner, vault, state, signers @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Validation Checks
+
+        // Check 1: The vault must be initialized and belong to the owner.
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index.to_le_bytes(), vault.key())?;
+
+        // Check 2: Only the owner (or the vault's multisig quorum) can hand the vault over.
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 3: A frozen vault can't be handed over until it is thawed.
+        vault_state.check_not_frozen()?;
+
+        Ok(Self { state })
+    }
+}
+
+pub struct TransferOwnershipInstructionData {
+    pub index: u64,
+    pub new_owner: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for TransferOwnershipInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. Check data length.
+        // [index: u64][new_owner: Pubkey]
+        if data.len() != size_of::<u64>() + size_of::<Pubkey>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // 2. Parse the data.
+        let index = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let new_owner = data[8..40].try_into().unwrap();
+
+        Ok(Self { index, new_owner })
+    }
+}
+
+pub struct TransferOwnership<'a> {
+    pub accounts: TransferOwnershipAccounts<'a>,
+    pub instruction_data: TransferOwnershipInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for TransferOwnership<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = TransferOwnershipInstructionData::try_from(data)?;
+        let accounts = TransferOwnershipAccounts::try_from((accounts, instruction_data.index))?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TransferOwnership<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &18;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // Replaces any earlier proposal. All zeroes cancels it.
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        vault_state.set_pending_owner(&self.instruction_data.new_owner);
+        vault_state.set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
@@ -1,80 +1,253 @@
+use core::mem::size_of;
 use pinocchio::{
     account_info::AccountInfo,
     instruction::{Seed, Signer},
     program_error::ProgramError,
-    pubkey::find_program_address,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
     ProgramResult,
 };
 use pinocchio_system::instructions::Transfer;
 
+use crate::{
+    Allowlist, DelegateRecord, PendingWithdrawal, VaultError, VaultState, ZeroCopyAccount,
+};
+
+// Who authorizes a withdrawal.
+pub enum WithdrawAuthority<'a> {
+    // The vault owner signed the transaction or, for a multisig vault, a quorum of members
+    // signed. The members are the remaining accounts after the allowlist, if any.
+    Owner,
+    // A delegate signed, spending the allowance recorded in its delegate record.
+    Delegate {
+        delegate: &'a AccountInfo,
+        record: &'a AccountInfo,
+    },
+    // A withdrawal queued by RequestWithdraw whose delay has elapsed. Nobody needs to sign:
+    // the authority approved it when it was requested.
+    Pending {
+        pending: &'a AccountInfo,
+    },
+    // The vault's heir signed a Claim after the owner stopped showing activity.
+    Heir {
+        heir: &'a AccountInfo,
+    },
+}
+
 // Structure to hold the accounts for the Withdraw instruction.
 // Pinocchio requires manual definition and parsing of accounts.
 pub struct WithdrawAccounts<'a> {
     pub owner: &'a AccountInfo,
     pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    // Account credited with the withdrawn lamports: the owner for Withdraw, any account for WithdrawTo.
+    pub destination: &'a AccountInfo,
+    pub authority: WithdrawAuthority<'a>,
+    // Vault creator and little-endian vault index, kept around because they are part of the signer seeds.
+    pub creator: Pubkey,
+    pub index: [u8; 8],
     pub bumps: [u8; 1],
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for WithdrawAccounts<'a> {
     type Error = ProgramError;
 
     // Parses and validates the accounts from the slice provided by the entrypoint.
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
         // 1. Unpack the accounts
-        // We expect: [owner, vault, system_program (optional/implied)]
-        let [owner, vault, _] = accounts else {
+        // We expect: [owner, vault, state, system_program (optional/implied), allowlist (if the vault
+        //             has one), ...multisig signers]
+        let [owner, vault, state, _, remaining @ ..] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        // 2. Perform Checks
-
-        // Check 1: Ensure the owner is a signer.
-        // We only allow withdrawal to the account that signed the transaction.
-        if !owner.is_signer() {
-            return Err(ProgramError::InvalidAccountOwner);
-        }
+        // 2. Perform Checks (the owner authorizes and the funds go back to the owner).
+        Self::new(
+            owner,
+            vault,
+            state,
+            owner,
+            WithdrawAuthority::Owner,
+            index,
+            remaining,
+        )
+    }
+}
 
-        // Check 2: Verify the vault's owner.
+impl<'a> WithdrawAccounts<'a> {
+    // Validation shared by every withdrawal path (Withdraw, WithdrawTo, DelegatedWithdraw,
+    // ExecuteWithdraw, Claim), which only differ in who authorizes the withdrawal and where the lamports go.
+    // `remaining` are the accounts following the instruction's fixed accounts: the vault's
+    // allowlist if it has one, then the multisig signers.
+    pub fn new(
+        owner: &'a AccountInfo,
+        vault: &'a AccountInfo,
+        state: &'a AccountInfo,
+        destination: &'a AccountInfo,
+        authority: WithdrawAuthority<'a>,
+        index: u64,
+        remaining: &'a [AccountInfo],
+    ) -> Result<Self, ProgramError> {
+        // Check 1: Verify the vault's owner.
         // The vault should be owned by the system program (since it holds lamports and is a PDA).
         // Wait, usually the vault is a PDA of THIS program.
         if !vault.is_owned_by(&pinocchio_system::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
+            return Err(VaultError::InvalidVaultOwner.into());
         }
 
-        // Check 3: Business Logic / Data Validity
+        // Check 2: Business Logic / Data Validity
         // We ensure the vault is not empty before attempting to withdraw.
         if vault.lamports().eq(&0) {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(VaultError::VaultEmpty.into());
         }
 
+        // Check 3: The vault must have been initialized.
+        let vault_state = VaultState::from_account_info(state)?;
+
         // Check 4: PDA Validation
         // We re-derive the PDA address to ensure the 'vault' account passed is the correct one.
-        // Seeds: "vault" + owner_pubkey
-        let (vault_key, bump) = find_program_address(&[b"vault", owner.key().as_ref()], &crate::ID);
-        if &vault_key != vault.key() {
-            return Err(ProgramError::InvalidAccountOwner);
+        // Seeds: "vault" + creator_pubkey + index, with the canonical bump stored in the state.
+        // The owner account must be the vault's current owner.
+        let index = index.to_le_bytes();
+        vault_state.check_vault(owner.key(), &index, vault.key())?;
+        let creator = *vault_state.creator();
+        let bump = vault_state.bump();
+
+        // Check 5: Ensure the withdrawal is authorized.
+        // Either the owner signed (or, for a multisig vault, a quorum of its members did),
+        // or a delegate signed and presented its record for this vault,
+        // or a pending withdrawal of this vault paying this destination is ready,
+        // or the heir signed once the owner has been inactive long enough.
+        // The allowance itself is checked once the amount is known.
+        let now = Clock::get()?.unix_timestamp;
+        let (allowlist, signers) = vault_state.split_allowlist(remaining)?;
+        match &authority {
+            WithdrawAuthority::Owner => {
+                vault_state.check_authority(owner, signers)?;
+            }
+            WithdrawAuthority::Delegate { delegate, record } => {
+                if !delegate.is_signer() {
+                    return Err(VaultError::NotSigner.into());
+                }
+                DelegateRecord::from_account_info(record)?
+                    .check_keys(vault.key(), delegate.key())?;
+            }
+            WithdrawAuthority::Pending { pending } => {
+                PendingWithdrawal::from_account_info(pending)?.check_ready(
+                    vault.key(),
+                    destination.key(),
+                    now,
+                )?;
+            }
+            WithdrawAuthority::Heir { heir } => {
+                if !heir.is_signer() {
+                    return Err(VaultError::NotSigner.into());
+                }
+                vault_state.check_claim(heir.key(), now)?;
+            }
+        }
+
+        // Vaults with a withdrawal delay only pay out through the queue.
+        // A claim already waited out the delay (see `check_claim`).
+        if !matches!(
+            authority,
+            WithdrawAuthority::Pending { .. } | WithdrawAuthority::Heir { .. }
+        ) && vault_state.withdraw_delay(now) > 0
+        {
+            return Err(VaultError::WithdrawalDelayActive.into());
+        }
+
+        // Check 6: Time lock, and the vault must not be frozen.
+        vault_state.check_unlocked(now)?;
+        vault_state.check_not_frozen()?;
+
+        // Check 7: Paying the vault back into itself would only inflate the withdrawal counter.
+        if destination.key().eq(vault.key()) {
+            return Err(VaultError::InvalidDestination.into());
+        }
+
+        // Check 8: Vaults with an allowlist only pay into its active destinations.
+        if let Some(allowlist) = allowlist {
+            let allowlist = Allowlist::from_account_info(allowlist)?;
+            allowlist.check_vault(vault.key())?;
+            allowlist.check_destination(destination.key(), now)?;
         }
 
         Ok(Self {
             owner,
             vault,
+            state,
+            destination,
+            authority,
+            creator,
+            index,
             bumps: [bump],
         })
     }
 }
 
+// Instruction data for Withdraw.
+// The vault index is required; the amount is optional and omitting it keeps the
+// original "withdraw everything" behaviour.
+pub struct WithdrawInstructionData {
+    pub index: u64,
+    pub amount: Option<u64>,
+}
+
+impl<'a> TryFrom<&'a [u8]> for WithdrawInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // 1. The first 8 bytes always select the vault.
+        if data.len() < size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (index, data) = data.split_at(size_of::<u64>());
+        let index = u64::from_le_bytes(index.try_into().unwrap());
+
+        // 2. No amount means "withdraw all".
+        if data.is_empty() {
+            return Ok(Self {
+                index,
+                amount: None,
+            });
+        }
+
+        // 3. Otherwise we expect exactly 8 more bytes for a little-endian u64 amount.
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data.try_into().unwrap());
+
+        // 4. An explicit amount of zero is almost certainly a client bug, so reject it
+        // instead of silently doing nothing.
+        if amount.eq(&0) {
+            return Err(VaultError::ZeroAmount.into());
+        }
+
+        Ok(Self {
+            index,
+            amount: Some(amount),
+        })
+    }
+}
+
 pub struct Withdraw<'a> {
     pub accounts: WithdrawAccounts<'a>,
+    // The resolved number of lamports to move out of the vault.
+    pub amount: u64,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for Withdraw<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Withdraw<'a> {
     type Error = ProgramError;
 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        // Since Withdraw doesn't have instruction data (args), we only parse accounts.
-        let accounts = WithdrawAccounts::try_from(accounts)?;
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // Parse the data first, since the accounts need the vault index to validate the PDA.
+        let instruction_data = WithdrawInstructionData::try_from(data)?;
+        let accounts = WithdrawAccounts::try_from((accounts, instruction_data.index))?;
 
-        Ok(Self { accounts })
+        Self::new(accounts, instruction_data)
     }
 }
 
@@ -82,14 +255,57 @@ impl<'a> Withdraw<'a> {
     // Unique discriminator for the Withdraw instruction (1).
     pub const DISCRIMINATOR: &'a u8 = &1;
 
+    // Checks the requested amount against the validated accounts.
+    pub fn new(
+        accounts: WithdrawAccounts<'a>,
+        instruction_data: WithdrawInstructionData,
+    ) -> Result<Self, ProgramError> {
+        // Resolve the amount against the current balance.
+        // `None` drains the vault, exactly like the original instruction did.
+        let balance = accounts.vault.lamports();
+        let amount = instruction_data.amount.unwrap_or(balance);
+
+        // We can't withdraw more than the vault holds.
+        if amount > balance {
+            return Err(VaultError::InsufficientFunds.into());
+        }
+
+        // A partial withdrawal must leave the vault rent-exempt, otherwise the runtime
+        // rejects the transaction with a much less helpful error.
+        let remaining = balance - amount;
+        if remaining.ne(&0) && remaining < Rent::get()?.minimum_balance(0) {
+            return Err(VaultError::WithdrawLeavesDust.into());
+        }
+
+        // Vesting: only the vested part of the deposits that hasn't been withdrawn yet can leave the vault.
+        let clock = Clock::get()?;
+        let vault_state = VaultState::from_account_info(accounts.state)?;
+        if vault_state.has_schedule()
+            && amount > vault_state.withdrawable_amount(clock.unix_timestamp)
+        {
+            return Err(VaultError::NotVested.into());
+        }
+
+        // Rate limit: whoever authorizes the withdrawal, the vault can only lose so much per window.
+        vault_state.check_rate_limit(amount, &clock)?;
+
+        // Delegated withdrawals are limited by the allowance, its expiry and its per-transaction cap.
+        if let WithdrawAuthority::Delegate { record, .. } = &accounts.authority {
+            DelegateRecord::from_account_info(record)?.check_spend(amount, clock.slot)?;
+        }
+
+        Ok(Self { accounts, amount })
+    }
+
     // Execution logic
     pub fn process(&mut self) -> ProgramResult {
         // 1. Prepare PDA Signers
         // The vault must sign to transfer funds out (since it's a PDA).
-        // `find_program_address` returned the canonical bump, which we use here.
+        // The canonical bump was stored in the vault state by `Initialize`.
         let seeds = [
             Seed::from(b"vault"),
-            Seed::from(self.accounts.owner.key().as_ref()),
+            Seed::from(self.accounts.creator.as_ref()),
+            Seed::from(&self.accounts.index),
             Seed::from(&self.accounts.bumps),
         ];
         let signers = [Signer::from(&seeds)];
@@ -99,11 +315,33 @@ impl<'a> Withdraw<'a> {
         // Signers are required because 'from' is a PDA.
         Transfer {
             from: self.accounts.vault,
-            to: self.accounts.owner,
-            lamports: self.accounts.vault.lamports(),
+            to: self.accounts.destination,
+            lamports: self.amount,
         }
         .invoke_signed(&signers)?;
 
+        // 3. Keep the counters in the vault state up to date.
+        let clock = Clock::get()?;
+        let mut vault_state = VaultState::from_account_info_mut(self.accounts.state)?;
+        let total_withdrawn = vault_state
+            .total_withdrawn()
+            .checked_add(self.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        vault_state.set_total_withdrawn(total_withdrawn);
+        vault_state.record_rate_limited(self.amount, &clock);
+
+        // A withdrawal the owner authorized shows the owner is still around.
+        if let WithdrawAuthority::Owner = self.accounts.authority {
+            vault_state.set_last_active(clock.unix_timestamp);
+        }
+
+        // 4. Spend the delegate's allowance (`check_spend` guaranteed it covers the amount).
+        if let WithdrawAuthority::Delegate { record, .. } = &self.accounts.authority {
+            let mut record = DelegateRecord::from_account_info_mut(record)?;
+            let allowance = record.allowance() - self.amount;
+            record.set_allowance(allowance);
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,46 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Withdraw, WithdrawAccounts, WithdrawAuthority, WithdrawInstructionData};
+
+// WithdrawTo is Withdraw with an explicit destination.
+// The owner still signs as the authority, but the lamports are credited to `destination`
+// (e.g. a cold wallet or an exchange deposit address). All other checks are the same as Withdraw.
+pub struct WithdrawTo<'a> {
+    pub withdraw: Withdraw<'a>,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for WithdrawTo<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        // Same instruction data as Withdraw: [index: u64][amount: u64 (optional)].
+        let instruction_data = WithdrawInstructionData::try_from(data)?;
+
+        // We expect: [owner, vault, state, destination, system_program, allowlist (if the vault
+        //             has one), ...multisig signers]
+        let [owner, vault, state, destination, _, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let accounts = WithdrawAccounts::new(
+            owner,
+            vault,
+            state,
+            destination,
+            WithdrawAuthority::Owner,
+            instruction_data.index,
+            remaining,
+        )?;
+
+        Ok(Self {
+            withdraw: Withdraw::new(accounts, instruction_data)?,
+        })
+    }
+}
+
+impl<'a> WithdrawTo<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+
+    pub fn process(&mut self) -> ProgramResult {
+        self.withdraw.process()
+    }
+}
@@ -0,0 +1,189 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    token::TransferChecked,
+    token::{associated_token_address, check_token_program, mint_decimals, token_account_amount},
+    Allowlist, VaultError, VaultState, WithdrawInstructionData, ZeroCopyAccount,
+};
+
+// Accounts for withdrawing SPL tokens from a vault back to the owner.
+pub struct WithdrawTokenAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub state: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub vault_token_account: &'a AccountInfo,
+    pub owner_token_account: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub decimals: u8,
+    // Current token balance of the vault token account.
+    pub balance: u64,
+    // Vault creator and little-endian vault index, part of the signer seeds.
+    pub creator: Pubkey,
+    pub index: [u8; 8],
+    pub bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], u64)> for WithdrawTokenAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, index): (&'a [AccountInfo], u64)) -> Result<Self, Self::Error> {
+        // 1. Unpack the accounts
+        // We expect: [owner, vault, state, mint, vault_token_account, owner_token_account, token_program,
+        //             allowlist (only if the vault has one), ...multisig signers]
+        let [owner, vault, state, mint, vault_token_account, owner_token_account, token_program, remaining @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // 2. Perform Checks
+
+        // Check 1: Only the legacy Token program and Token-2022 are supported.
+        check_token_program(token_program)?;
+
+        // Check 2: The mint must belong to that token program.
+        let decimals = mint_decimals(mint, token_program.key())?;
+
+        // Check 3: PDA Validation
+        // The vault PDA signs the transfer, so we need the canonical bump stored in the vault state.
+        let index = index.to_le_bytes();
+        let vault_state = VaultState::from_account_info(state)?;
+        vault_state.check_vault(owner.key(), &index, vault.key())?;
+        let creator = *vault_state.creator();
+        let bump = vault_state.bump();
+
+        // Check 4: The owner (or the vault's multisig quorum) must approve the withdrawal.
+        let (allowlist, signers) = vault_state.split_allowlist(remaining)?;
+        vault_state.check_authority(owner, signers)?;
+
+        // Check 5: Time lock and freeze. They cover every asset held by the vault, not only SOL.
+        let now = Clock::get()?.unix_timestamp;
+        vault_state.check_unlocked(now)?;
+        vault_state.check_not_frozen()?;
+
+        // The vesting schedule only tracks lamports, so tokens stay locked until it ends.
+        if now < vault_state.end_ts() {
+            return Err(VaultError::NotVested.into());
+        }
+
+        // Likewise, the withdrawal queue only handles lamports: tokens can't leave a vault with
+        // a withdrawal delay until the delay is lowered back to zero.
+        if vault_state.withdraw_delay(now) > 0 {
+            return Err(VaultError::WithdrawalDelayActive.into());
+        }
+
+        // Check 6: The source must be the vault's associated token account for this mint.
+        if vault_token_account.key().ne(&associated_token_address(
+            vault.key(),
+            mint.key(),
+            token_program.key(),
+        )) {
+            return Err(VaultError::InvalidTokenAccount.into());
+        }
+        let balance = token_account_amount(
+            vault_token_account,
+            token_program.key(),
+            mint.key(),
+            vault.key(),
+        )?;
+
+        // Check 7: Tokens can only go back to a token account controlled by the owner.
+        token_account_amount(
+            owner_token_account,
+            token_program.key(),
+            mint.key(),
+            owner.key(),
+        )?;
+
+        // Check 8: Vaults with an allowlist only pay into its active destinations, tokens included.
+        // The destination of a token withdrawal is the wallet owning the receiving token account,
+        // which Check 7 pinned to the owner.
+        if let Some(allowlist) = allowlist {
+            let allowlist = Allowlist::from_account_info(allowlist)?;
+            allowlist.check_vault(vault.key())?;
+            allowlist.check_destination(owner.key(), now)?;
+        }
+
+        Ok(Self {
+            owner,
+            vault,
+            state,
+            mint,
+            vault_token_account,
+            owner_token_account,
+            token_program,
+            decimals,
+            balance,
+            creator,
+            index,
+            bumps: [bump],
+        })
+    }
+}
+
+// WithdrawToken uses the same instruction data as Withdraw: [index: u64][amount: u64 (optional)].
+pub struct WithdrawToken<'a> {
+    pub accounts: WithdrawTokenAccounts<'a>,
+    // The resolved number of tokens to move out of the vault.
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for WithdrawToken<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let instruction_data = WithdrawInstructionData::try_from(data)?;
+        let accounts = WithdrawTokenAccounts::try_from((accounts, instruction_data.index))?;
+
+        // `None` withdraws the whole token balance.
+        let amount = instruction_data.amount.unwrap_or(accounts.balance);
+        if amount.eq(&0) {
+            return Err(VaultError::VaultEmpty.into());
+        }
+        if amount > accounts.balance {
+            return Err(VaultError::InsufficientFunds.into());
+        }
+
+        Ok(Self { accounts, amount })
+    }
+}
+
+impl<'a> WithdrawToken<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // The vault PDA is the authority of the vault token account, so it signs the transfer.
+        let seeds = [
+            Seed::from(b"vault"),
+            Seed::from(self.accounts.creator.as_ref()),
+            Seed::from(&self.accounts.index),
+            Seed::from(&self.accounts.bumps),
+        ];
+        let signers = [Signer::from(&seeds)];
+
+        TransferChecked {
+            from: self.accounts.vault_token_account,
+            mint: self.accounts.mint,
+            to: self.accounts.owner_token_account,
+            authority: self.accounts.vault,
+            token_program: self.accounts.token_program.key(),
+            amount: self.amount,
+            decimals: self.accounts.decimals,
+        }
+        .invoke_signed(&signers)?;
+
+        // The owner authorized the withdrawal, so it is still around.
+        VaultState::from_account_info_mut(self.accounts.state)?
+            .set_last_active(Clock::get()?.unix_timestamp);
+
+        Ok(())
+    }
+}
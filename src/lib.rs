@@ -1,30 +1,123 @@
 #![no_std]
 
 use pinocchio::{
-    account_info::AccountInfo, entrypoint, nostd_panic_handler, program_error::ProgramError,
-    pubkey::Pubkey, ProgramResult,
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
 };
 
-entrypoint!(process_instruction);
-nostd_panic_handler!();
+#[cfg(not(feature = "no-entrypoint"))]
+pinocchio::entrypoint!(process_instruction);
+#[cfg(not(feature = "no-entrypoint"))]
+pinocchio::nostd_panic_handler!();
+
+// Without our panic handler, host builds take std's. On-chain, the program depending on this
+// crate provides it.
+#[cfg(all(feature = "no-entrypoint", not(target_os = "solana")))]
+extern crate std;
+
+#[cfg(feature = "client")]
+pub mod client;
+
+#[cfg(feature = "cpi")]
+pub mod cpi;
+
+pub mod error;
+pub use error::*;
 
 pub mod instructions;
 pub use instructions::*;
 
+pub mod state;
+pub use state::*;
+
+pub mod token;
+
 // 22222222222222222222222222222222222222222222
 pub const ID: Pubkey = [
     0x0f, 0x1e, 0x6b, 0x14, 0x21, 0xc0, 0x4a, 0x07, 0x04, 0x31, 0x26, 0x5c, 0x19, 0xc5, 0xbb, 0xee,
     0x19, 0x92, 0xba, 0xe8, 0xaf, 0xd1, 0xcd, 0x07, 0x8e, 0xf8, 0xaf, 0x70, 0x47, 0xdc, 0x11, 0xf7,
 ];
 
-fn process_instruction(
+pub fn process_instruction(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
     match instruction_data.split_first() {
         Some((Deposit::DISCRIMINATOR, data)) => Deposit::try_from((data, accounts))?.process(),
-        Some((Withdraw::DISCRIMINATOR, _)) => Withdraw::try_from(accounts)?.process(),
+        // Withdraws from a vault created before vaults had an index and a state account.
+        Some((Withdraw::DISCRIMINATOR, [])) => LegacyWithdraw::try_from(accounts)?.process(),
+        Some((Withdraw::DISCRIMINATOR, data)) => Withdraw::try_from((data, accounts))?.process(),
+        Some((DepositToken::DISCRIMINATOR, data)) => {
+            DepositToken::try_from((data, accounts))?.process()
+        }
+        Some((WithdrawToken::DISCRIMINATOR, data)) => {
+            WithdrawToken::try_from((data, accounts))?.process()
+        }
+        Some((Initialize::DISCRIMINATOR, data)) => {
+            Initialize::try_from((data, accounts))?.process()
+        }
+        Some((GetVested::DISCRIMINATOR, _)) => GetVested::try_from(accounts)?.process(),
+        Some((WithdrawTo::DISCRIMINATOR, data)) => {
+            WithdrawTo::try_from((data, accounts))?.process()
+        }
+        Some((Approve::DISCRIMINATOR, data)) => Approve::try_from((data, accounts))?.process(),
+        Some((Revoke::DISCRIMINATOR, data)) => Revoke::try_from((data, accounts))?.process(),
+        Some((DelegatedWithdraw::DISCRIMINATOR, data)) => {
+            DelegatedWithdraw::try_from((data, accounts))?.process()
+        }
+        Some((SetMultisig::DISCRIMINATOR, data)) => {
+            SetMultisig::try_from((data, accounts))?.process()
+        }
+        Some((RequestWithdraw::DISCRIMINATOR, data)) => {
+            RequestWithdraw::try_from((data, accounts))?.process()
+        }
+        Some((ExecuteWithdraw::DISCRIMINATOR, data)) => {
+            ExecuteWithdraw::try_from((data, accounts))?.process()
+        }
+        Some((CancelWithdraw::DISCRIMINATOR, data)) => {
+            CancelWithdraw::try_from((data, accounts))?.process()
+        }
+        Some((SetWithdrawDelay::DISCRIMINATOR, data)) => {
+            SetWithdrawDelay::try_from((data, accounts))?.process()
+        }
+        Some((SetRateLimit::DISCRIMINATOR, data)) => {
+            SetRateLimit::try_from((data, accounts))?.process()
+        }
+        Some((AddDestination::DISCRIMINATOR, data)) => {
+            AddDestination::try_from((data, accounts))?.process()
+        }
+        Some((RemoveDestination::DISCRIMINATOR, data)) => {
+            RemoveDestination::try_from((data, accounts))?.process()
+        }
+        Some((TransferOwnership::DISCRIMINATOR, data)) => {
+            TransferOwnership::try_from((data, accounts))?.process()
+        }
+        Some((AcceptOwnership::DISCRIMINATOR, data)) => {
+            AcceptOwnership::try_from((data, accounts))?.process()
+        }
+        Some((SetGuardians::DISCRIMINATOR, data)) => {
+            SetGuardians::try_from((data, accounts))?.process()
+        }
+        Some((ProposeRecovery::DISCRIMINATOR, data)) => {
+            ProposeRecovery::try_from((data, accounts))?.process()
+        }
+        Some((CancelRecovery::DISCRIMINATOR, data)) => {
+            CancelRecovery::try_from((data, accounts))?.process()
+        }
+        Some((ExecuteRecovery::DISCRIMINATOR, data)) => {
+            ExecuteRecovery::try_from((data, accounts))?.process()
+        }
+        Some((Heartbeat::DISCRIMINATOR, data)) => Heartbeat::try_from((data, accounts))?.process(),
+        Some((SetInheritance::DISCRIMINATOR, data)) => {
+            SetInheritance::try_from((data, accounts))?.process()
+        }
+        Some((Claim::DISCRIMINATOR, data)) => Claim::try_from((data, accounts))?.process(),
+        Some((Close::DISCRIMINATOR, data)) => Close::try_from((data, accounts))?.process(),
+        Some((SetFreezeAuthority::DISCRIMINATOR, data)) => {
+            SetFreezeAuthority::try_from((data, accounts))?.process()
+        }
+        Some((Freeze::DISCRIMINATOR, data)) => Freeze::try_from((data, accounts))?.process(),
+        Some((Thaw::DISCRIMINATOR, data)) => Thaw::try_from((data, accounts))?.process(),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
@@ -0,0 +1,158 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{VaultError, ZeroCopyAccount};
+
+// One approved withdrawal destination.
+#[repr(C)]
+pub struct AllowlistEntry {
+    destination: Pubkey,
+    // Unix timestamp from which the destination can receive withdrawals.
+    active_ts: [u8; 8],
+}
+
+impl AllowlistEntry {
+    pub fn destination(&self) -> &Pubkey {
+        &self.destination
+    }
+
+    pub fn active_ts(&self) -> i64 {
+        i64::from_le_bytes(self.active_ts)
+    }
+}
+
+// Program-owned account at `["allowlist", vault]`, created by the first `AddDestination`.
+// Once a vault has one, SOL withdrawals can only pay into its active destinations.
+#[repr(C)]
+pub struct Allowlist {
+    // Layout version, `Allowlist::VERSION`. Zero means the account was never initialized.
+    version: u8,
+    // Canonical bump of the allowlist PDA.
+    bump: u8,
+    // Vault whose withdrawals are restricted.
+    vault: Pubkey,
+    // Number of used entries, at the start of `entries`.
+    count: u8,
+    entries: [AllowlistEntry; Allowlist::MAX_ENTRIES],
+}
+
+unsafe impl ZeroCopyAccount for Allowlist {
+    const VERSION: u8 = 1;
+
+    const INVALID: VaultError = VaultError::InvalidAllowlist;
+}
+
+impl Allowlist {
+    pub const LEN: usize = size_of::<Self>();
+
+    pub const SEED: &'static [u8] = b"allowlist";
+
+    pub const MAX_ENTRIES: usize = 16;
+
+    // Seconds before an added destination can receive withdrawals.
+    pub const ADD_DELAY: i64 = 24 * 60 * 60;
+
+    // Verifies that `allowlist` is the allowlist PDA of `vault` and returns its bump.
+    // Only needed when creating the account; afterwards the stored vault binds it.
+    pub fn check_address(allowlist: &AccountInfo, vault: &Pubkey) -> Result<u8, ProgramError> {
+        let (allowlist_key, bump) = find_program_address(&[Self::SEED, vault.as_ref()], &crate::ID);
+        if allowlist.key().ne(&allowlist_key) {
+            return Err(VaultError::InvalidAllowlist.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+
+    pub fn vault(&self) -> &Pubkey {
+        &self.vault
+    }
+
+    pub fn set_vault(&mut self, vault: &Pubkey) {
+        self.vault = *vault;
+    }
+
+    pub fn entries(&self) -> &[AllowlistEntry] {
+        &self.entries[..self.count as usize]
+    }
+
+    // Fails unless the allowlist belongs to `vault`.
+    pub fn check_vault(&self, vault: &Pubkey) -> ProgramResult {
+        if self.vault.ne(vault) {
+            return Err(VaultError::InvalidAllowlist.into());
+        }
+
+        Ok(())
+    }
+
+    // Adds `destination`, receiving withdrawals from `active_ts` on.
+    // Adding a destination that is already listed is an error rather than a reset of its timelock.
+    pub fn add(&mut self, destination: &Pubkey, active_ts: i64) -> ProgramResult {
+        if self
+            .entries()
+            .iter()
+            .any(|entry| entry.destination.eq(destination))
+        {
+            return Err(VaultError::InvalidAllowlist.into());
+        }
+
+        let count = self.count as usize;
+        if count >= Self::MAX_ENTRIES {
+            return Err(VaultError::AllowlistFull.into());
+        }
+
+        self.entries[count] = AllowlistEntry {
+            destination: *destination,
+            active_ts: active_ts.to_le_bytes(),
+        };
+        self.count += 1;
+
+        Ok(())
+    }
+
+    // Removes `destination`, moving the last entry into its slot.
+    pub fn remove(&mut self, destination: &Pubkey) -> ProgramResult {
+        let Some(position) = self
+            .entries()
+            .iter()
+            .position(|entry| entry.destination.eq(destination))
+        else {
+            return Err(VaultError::DestinationNotAllowed.into());
+        };
+
+        let last = self.count as usize - 1;
+        self.entries.swap(position, last);
+        self.entries[last] = AllowlistEntry {
+            destination: [0; 32],
+            active_ts: [0; 8],
+        };
+        self.count -= 1;
+
+        Ok(())
+    }
+
+    // Fails unless `destination` is listed and its timelock has passed at `now`.
+    pub fn check_destination(&self, destination: &Pubkey, now: i64) -> ProgramResult {
+        if !self
+            .entries()
+            .iter()
+            .any(|entry| entry.destination.eq(destination) && entry.active_ts() <= now)
+        {
+            return Err(VaultError::DestinationNotAllowed.into());
+        }
+
+        Ok(())
+    }
+}
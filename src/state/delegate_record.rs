@@ -0,0 +1,133 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{VaultError, ZeroCopyAccount};
+
+// Program-owned account at `["delegate", vault, delegate]`, created by `Approve`.
+// It lets `delegate` withdraw up to `allowance` lamports from `vault` without the owner signing.
+#[repr(C)]
+pub struct DelegateRecord {
+    // Layout version, `DelegateRecord::VERSION`. Zero means the account was never initialized.
+    version: u8,
+    // Canonical bump of the record PDA.
+    bump: u8,
+    // Vault the allowance applies to.
+    vault: Pubkey,
+    // Key allowed to spend the allowance.
+    delegate: Pubkey,
+    // Lamports the delegate may still withdraw. Decremented by every delegated withdrawal.
+    allowance: [u8; 8],
+    // Last slot in which the allowance can be used. Zero means it never expires.
+    expiry_slot: [u8; 8],
+    // Maximum lamports per withdrawal. Zero means no per-transaction cap.
+    per_tx_cap: [u8; 8],
+}
+
+unsafe impl ZeroCopyAccount for DelegateRecord {
+    const VERSION: u8 = 1;
+
+    const INVALID: VaultError = VaultError::InvalidDelegateRecord;
+}
+
+impl DelegateRecord {
+    pub const LEN: usize = size_of::<Self>();
+
+    pub const SEED: &'static [u8] = b"delegate";
+
+    // Verifies that `record` is the delegate record PDA of `vault` and `delegate` and returns its bump.
+    // Only needed when creating the record; afterwards the stored keys bind it to its vault and delegate.
+    pub fn check_address(
+        record: &AccountInfo,
+        vault: &Pubkey,
+        delegate: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        let (record_key, bump) =
+            find_program_address(&[Self::SEED, vault.as_ref(), delegate.as_ref()], &crate::ID);
+        if record.key().ne(&record_key) {
+            return Err(VaultError::InvalidDelegateRecord.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+
+    pub fn vault(&self) -> &Pubkey {
+        &self.vault
+    }
+
+    pub fn set_vault(&mut self, vault: &Pubkey) {
+        self.vault = *vault;
+    }
+
+    pub fn delegate(&self) -> &Pubkey {
+        &self.delegate
+    }
+
+    pub fn set_delegate(&mut self, delegate: &Pubkey) {
+        self.delegate = *delegate;
+    }
+
+    pub fn allowance(&self) -> u64 {
+        u64::from_le_bytes(self.allowance)
+    }
+
+    pub fn set_allowance(&mut self, allowance: u64) {
+        self.allowance = allowance.to_le_bytes();
+    }
+
+    pub fn expiry_slot(&self) -> u64 {
+        u64::from_le_bytes(self.expiry_slot)
+    }
+
+    pub fn set_expiry_slot(&mut self, expiry_slot: u64) {
+        self.expiry_slot = expiry_slot.to_le_bytes();
+    }
+
+    pub fn per_tx_cap(&self) -> u64 {
+        u64::from_le_bytes(self.per_tx_cap)
+    }
+
+    pub fn set_per_tx_cap(&mut self, per_tx_cap: u64) {
+        self.per_tx_cap = per_tx_cap.to_le_bytes();
+    }
+
+    // Fails unless the record belongs to `vault` and `delegate`.
+    pub fn check_keys(&self, vault: &Pubkey, delegate: &Pubkey) -> ProgramResult {
+        if self.vault.ne(vault) || self.delegate.ne(delegate) {
+            return Err(VaultError::InvalidDelegateRecord.into());
+        }
+
+        Ok(())
+    }
+
+    // Fails unless the delegate may withdraw `amount` lamports in `slot`.
+    pub fn check_spend(&self, amount: u64, slot: u64) -> ProgramResult {
+        let expiry_slot = self.expiry_slot();
+        if expiry_slot.ne(&0) && slot > expiry_slot {
+            return Err(VaultError::DelegationExpired.into());
+        }
+
+        let per_tx_cap = self.per_tx_cap();
+        if per_tx_cap.ne(&0) && amount > per_tx_cap {
+            return Err(VaultError::PerTransactionCapExceeded.into());
+        }
+
+        if amount > self.allowance() {
+            return Err(VaultError::AllowanceExceeded.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::{AccountInfo, Ref, RefMut},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::VaultError;
+
+pub mod allowlist;
+pub mod delegate_record;
+pub mod pending_withdrawal;
+pub mod recovery_state;
+pub mod vault_state;
+
+pub use allowlist::*;
+pub use delegate_record::*;
+pub use pending_withdrawal::*;
+pub use recovery_state::*;
+pub use vault_state::*;
+
+/// A program-owned account whose data is cast directly to `Self`, without any (de)serialization.
+/// The first byte is a layout version, where zero means the account was never initialized.
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` structs made only of `u8`s and byte arrays, so that they have
+/// an alignment of 1, every bit pattern is valid and the version is the first byte.
+pub unsafe trait ZeroCopyAccount: Sized {
+    // Current layout version.
+    const VERSION: u8;
+
+    // Returned for data that doesn't hold a valid, initialized `Self`.
+    const INVALID: VaultError;
+
+    // Returned by `from_account_info` for an account without data.
+    const NOT_INITIALIZED: VaultError = Self::INVALID;
+
+    // Returned by `init` for data that isn't freshly allocated.
+    const ALREADY_INITIALIZED: VaultError = Self::INVALID;
+
+    // Interprets the account data as `Self`.
+    fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.len() != size_of::<Self>() || data[0].ne(&Self::VERSION) {
+            return Err(Self::INVALID.into());
+        }
+        // SAFETY: The length was checked above and implementors have an alignment of 1.
+        Ok(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    // Interprets the account data as a mutable `Self`.
+    fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() != size_of::<Self>() || data[0].ne(&Self::VERSION) {
+            return Err(Self::INVALID.into());
+        }
+        // SAFETY: The length was checked above and implementors have an alignment of 1.
+        Ok(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    // Stamps freshly allocated (zeroed) account data with the current version and returns it.
+    fn init(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() != size_of::<Self>() || data[0].ne(&0) {
+            return Err(Self::ALREADY_INITIALIZED.into());
+        }
+        data[0] = Self::VERSION;
+        Self::load_mut(data)
+    }
+
+    // Borrows a program-owned account whose address was already checked.
+    fn from_account_info(account: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        if account.data_is_empty() {
+            return Err(Self::NOT_INITIALIZED.into());
+        }
+
+        if !account.is_owned_by(&crate::ID) {
+            return Err(Self::INVALID.into());
+        }
+
+        Ref::try_map(account.try_borrow_data()?, Self::load).map_err(|(_, error)| error)
+    }
+
+    // Mutable counterpart of `from_account_info`.
+    fn from_account_info_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        if account.data_is_empty() {
+            return Err(Self::NOT_INITIALIZED.into());
+        }
+
+        if !account.is_owned_by(&crate::ID) {
+            return Err(Self::INVALID.into());
+        }
+
+        RefMut::try_map(account.try_borrow_mut_data()?, Self::load_mut).map_err(|(_, error)| error)
+    }
+}
+
+// Moves the rent of a program-owned account to `recipient` and closes it.
+// The account is program-owned, so we can debit it directly without a CPI.
+pub fn close_program_account(account: &AccountInfo, recipient: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    *recipient.try_borrow_mut_lamports()? += lamports;
+    *account.try_borrow_mut_lamports()? = 0;
+
+    account.close()
+}
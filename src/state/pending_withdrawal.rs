@@ -0,0 +1,113 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{VaultError, ZeroCopyAccount};
+
+// Program-owned account at `["pending", vault, request_id]`, created by `RequestWithdraw`.
+// It records a queued withdrawal that `ExecuteWithdraw` can carry out once `ready_ts` is reached,
+// and that `CancelWithdraw` can drop before then.
+#[repr(C)]
+pub struct PendingWithdrawal {
+    // Layout version, `PendingWithdrawal::VERSION`. Zero means the account was never initialized.
+    version: u8,
+    // Canonical bump of the pending withdrawal PDA.
+    bump: u8,
+    // Vault the lamports are withdrawn from.
+    vault: Pubkey,
+    // Account credited when the withdrawal is executed.
+    destination: Pubkey,
+    // Lamports to withdraw.
+    amount: [u8; 8],
+    // Unix timestamp from which the withdrawal can be executed.
+    ready_ts: [u8; 8],
+}
+
+unsafe impl ZeroCopyAccount for PendingWithdrawal {
+    const VERSION: u8 = 1;
+
+    const INVALID: VaultError = VaultError::InvalidPendingWithdrawal;
+}
+
+impl PendingWithdrawal {
+    pub const LEN: usize = size_of::<Self>();
+
+    pub const SEED: &'static [u8] = b"pending";
+
+    // Verifies that `pending` is the pending withdrawal PDA of `vault` and `request_id` and
+    // returns its bump. Only needed when creating the account; afterwards the stored vault binds it.
+    pub fn check_address(
+        pending: &AccountInfo,
+        vault: &Pubkey,
+        request_id: &[u8; 8],
+    ) -> Result<u8, ProgramError> {
+        let (pending_key, bump) =
+            find_program_address(&[Self::SEED, vault.as_ref(), request_id], &crate::ID);
+        if pending.key().ne(&pending_key) {
+            return Err(VaultError::InvalidPendingWithdrawal.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+
+    pub fn vault(&self) -> &Pubkey {
+        &self.vault
+    }
+
+    pub fn set_vault(&mut self, vault: &Pubkey) {
+        self.vault = *vault;
+    }
+
+    pub fn destination(&self) -> &Pubkey {
+        &self.destination
+    }
+
+    pub fn set_destination(&mut self, destination: &Pubkey) {
+        self.destination = *destination;
+    }
+
+    pub fn amount(&self) -> u64 {
+        u64::from_le_bytes(self.amount)
+    }
+
+    pub fn set_amount(&mut self, amount: u64) {
+        self.amount = amount.to_le_bytes();
+    }
+
+    pub fn ready_ts(&self) -> i64 {
+        i64::from_le_bytes(self.ready_ts)
+    }
+
+    pub fn set_ready_ts(&mut self, ready_ts: i64) {
+        self.ready_ts = ready_ts.to_le_bytes();
+    }
+
+    // Fails unless this withdrawal belongs to `vault`, pays `destination` and is ready at `now`.
+    pub fn check_ready(&self, vault: &Pubkey, destination: &Pubkey, now: i64) -> ProgramResult {
+        if self.vault.ne(vault) {
+            return Err(VaultError::InvalidPendingWithdrawal.into());
+        }
+
+        if self.destination.ne(destination) {
+            return Err(VaultError::InvalidDestination.into());
+        }
+
+        if now < self.ready_ts() {
+            return Err(VaultError::WithdrawalNotReady.into());
+        }
+
+        Ok(())
+    }
+}
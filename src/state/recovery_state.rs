@@ -0,0 +1,100 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{VaultError, ZeroCopyAccount};
+
+// Program-owned account at `["recovery", vault]`, created by `ProposeRecovery`.
+// It records a guardian proposal to hand the vault to `new_owner`, which `ExecuteRecovery`
+// carries out once `ready_ts` is reached unless the owner cancels it first.
+#[repr(C)]
+pub struct RecoveryState {
+    // Layout version, `RecoveryState::VERSION`. Zero means the account was never initialized.
+    version: u8,
+    // Canonical bump of the recovery PDA.
+    bump: u8,
+    // Vault being recovered.
+    vault: Pubkey,
+    // Key that becomes the vault's owner.
+    new_owner: Pubkey,
+    // Account that paid for the proposal and gets its rent back when it is closed.
+    payer: Pubkey,
+    // Unix timestamp from which the recovery can be executed.
+    ready_ts: [u8; 8],
+}
+
+unsafe impl ZeroCopyAccount for RecoveryState {
+    const VERSION: u8 = 1;
+
+    const INVALID: VaultError = VaultError::InvalidRecovery;
+}
+
+impl RecoveryState {
+    pub const LEN: usize = size_of::<Self>();
+
+    pub const SEED: &'static [u8] = b"recovery";
+
+    // Verifies that `recovery` is the recovery PDA of `vault` and returns its bump.
+    // Only needed when creating the account; afterwards the stored vault binds it.
+    pub fn check_address(recovery: &AccountInfo, vault: &Pubkey) -> Result<u8, ProgramError> {
+        let (recovery_key, bump) = find_program_address(&[Self::SEED, vault.as_ref()], &crate::ID);
+        if recovery.key().ne(&recovery_key) {
+            return Err(VaultError::InvalidRecovery.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+
+    pub fn vault(&self) -> &Pubkey {
+        &self.vault
+    }
+
+    pub fn set_vault(&mut self, vault: &Pubkey) {
+        self.vault = *vault;
+    }
+
+    pub fn new_owner(&self) -> &Pubkey {
+        &self.new_owner
+    }
+
+    pub fn set_new_owner(&mut self, new_owner: &Pubkey) {
+        self.new_owner = *new_owner;
+    }
+
+    pub fn payer(&self) -> &Pubkey {
+        &self.payer
+    }
+
+    pub fn set_payer(&mut self, payer: &Pubkey) {
+        self.payer = *payer;
+    }
+
+    pub fn ready_ts(&self) -> i64 {
+        i64::from_le_bytes(self.ready_ts)
+    }
+
+    pub fn set_ready_ts(&mut self, ready_ts: i64) {
+        self.ready_ts = ready_ts.to_le_bytes();
+    }
+
+    // Fails unless this proposal belongs to `vault` and its rent goes back to `payer`.
+    pub fn check_keys(&self, vault: &Pubkey, payer: &Pubkey) -> ProgramResult {
+        if self.vault.ne(vault) || self.payer.ne(payer) {
+            return Err(VaultError::InvalidRecovery.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,692 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{create_program_address, find_program_address, Pubkey},
+    sysvars::clock::Clock,
+    ProgramResult,
+};
+
+use crate::{VaultError, ZeroCopyAccount};
+
+// Program-owned account living next to a vault PDA, at `["state", vault]`.
+// It is created by `Initialize` and stores the vault's configuration and on-chain metadata.
+//
+// The layout is zero-copy: every field is a byte array, so the struct has an alignment of 1
+// and can be cast directly from the account data without any (de)serialization step.
+// The layout is fixed; the leading version byte lets future layouts be told apart.
+#[repr(C)]
+pub struct VaultState {
+    // Layout version, `VaultState::VERSION`. Zero means the account was never initialized.
+    version: u8,
+    // Canonical bump of the vault PDA.
+    bump: u8,
+    // Key that created the vault, used with `index` in the vault seeds. It never changes, so the
+    // vault keeps its address when ownership moves to another key (see `owner`).
+    creator: Pubkey,
+    // Little-endian vault index (the other vault seed).
+    index: [u8; 8],
+    // Optional beneficiary of a vesting schedule, allowed to query the vested amount (see GetVested).
+    // All zeroes when unset.
+    beneficiary: Pubkey,
+    // Unix timestamp before which nothing can be withdrawn from the vault.
+    unlock_ts: [u8; 8],
+    // Linear vesting schedule. `end_ts == 0` means the vault has no schedule.
+    // `cliff_ts == 0` means the schedule has no cliff.
+    start_ts: [u8; 8],
+    cliff_ts: [u8; 8],
+    end_ts: [u8; 8],
+    // Lamports deposited into / withdrawn from the vault since it was initialized.
+    total_deposited: [u8; 8],
+    total_withdrawn: [u8; 8],
+    // Slot in which the vault was initialized.
+    created_slot: [u8; 8],
+    // Free-form label chosen by the owner (e.g. "payroll"), zero-padded.
+    label: [u8; 32],
+    // Optional M-of-N multisig controlling the vault. `threshold == 0` means the owner alone
+    // authorizes; otherwise `threshold` of the first `member_count` members must sign.
+    threshold: u8,
+    member_count: u8,
+    members: [Pubkey; VaultState::MAX_MEMBERS],
+    // Seconds a withdrawal has to wait between RequestWithdraw and ExecuteWithdraw.
+    // Zero means withdrawals are immediate.
+    withdraw_delay: [u8; 8],
+    // A lower delay only replaces `withdraw_delay` from `pending_delay_ts` on, so that a
+    // compromised key can't remove the delay and withdraw right away. Zero when none is scheduled.
+    pending_delay: [u8; 8],
+    pending_delay_ts: [u8; 8],
+    // Lamports that may leave the vault per window. Zero means no rate limit.
+    rate_limit: [u8; 8],
+    // Window length, `PERIOD_DAY` or `PERIOD_EPOCH`.
+    rate_period: u8,
+    // Start of the current window (unix timestamp of the day, or epoch number) and the lamports
+    // withdrawn in it so far.
+    window_start: [u8; 8],
+    window_spent: [u8; 8],
+    // A looser limit only replaces the current one when the next window starts, so that a
+    // compromised key can't lift the limit and drain the vault at once.
+    next_rate_limit: [u8; 8],
+    next_rate_period: u8,
+    rate_change_pending: u8,
+    // Non-zero once the vault has an allowlist: SOL withdrawals then need it as the first
+    // remaining account and can only pay into its destinations.
+    has_allowlist: u8,
+    // Current owner of the vault. Starts as the creator and changes with AcceptOwnership.
+    owner: Pubkey,
+    // Owner proposed by TransferOwnership until it accepts. All zeroes when none.
+    pending_owner: Pubkey,
+    // Optional guardians able to reassign the vault to a new owner if the owner loses its key.
+    // `guardian_threshold == 0` means the vault has no guardians; otherwise `guardian_threshold`
+    // of the first `guardian_count` guardians must sign a recovery proposal.
+    guardian_threshold: u8,
+    guardian_count: u8,
+    guardians: [Pubkey; VaultState::MAX_GUARDIANS],
+    // Seconds the owner has to cancel a recovery proposal before it can be executed.
+    challenge_period: [u8; 8],
+    // Seconds without owner activity after which the heir may claim the vault.
+    // Zero means the vault has no dead-man switch.
+    inactivity_period: [u8; 8],
+    // Key able to claim the vault once the owner is inactive, set by SetInheritance. Separate from
+    // `beneficiary`, so that naming an heir never changes who may view the vesting schedule.
+    // All zeroes when unset.
+    heir: Pubkey,
+    // Unix timestamp of the last instruction the owner authorized.
+    last_active: [u8; 8],
+    // Program-owned accounts tied to the vault: delegate records, pending withdrawals, the
+    // allowlist and an open recovery proposal. Close needs it back at zero, so that nothing left
+    // behind comes back to life if the vault is initialized again.
+    open_accounts: [u8; 4],
+    // Optional key able to freeze the vault during an incident. All zeroes when none.
+    freeze_authority: Pubkey,
+    // Non-zero while the vault is frozen: nothing can be withdrawn until the freeze authority thaws it.
+    frozen: u8,
+}
+
+// Rate limit window of a vault as of a given clock, see `VaultState::rate_window`.
+pub struct RateWindow {
+    pub limit: u64,
+    pub period: u8,
+    pub start: u64,
+    pub spent: u64,
+    // Whether a scheduled limit change was applied when the window rolled over.
+    pub next_applied: bool,
+}
+
+unsafe impl ZeroCopyAccount for VaultState {
+    const VERSION: u8 = 1;
+
+    const INVALID: VaultError = VaultError::InvalidStateAccount;
+
+    const NOT_INITIALIZED: VaultError = VaultError::StateNotInitialized;
+
+    const ALREADY_INITIALIZED: VaultError = VaultError::StateAlreadyInitialized;
+}
+
+impl VaultState {
+    pub const LEN: usize = size_of::<Self>();
+
+    pub const SEED: &'static [u8] = b"state";
+
+    pub const MAX_MEMBERS: usize = 10;
+
+    pub const MAX_GUARDIANS: usize = 10;
+
+    // Rate limit periods: UTC days (86 400 seconds) or Solana epochs.
+    pub const PERIOD_DAY: u8 = 0;
+    pub const PERIOD_EPOCH: u8 = 1;
+
+    const SECONDS_PER_DAY: i64 = 86_400;
+
+    // Verifies that `vault` is the vault described by this state and that `owner` currently owns it.
+    pub fn check_vault(&self, owner: &Pubkey, index: &[u8; 8], vault: &Pubkey) -> ProgramResult {
+        self.check_vault_address(index, vault)?;
+
+        if self.owner.ne(owner) {
+            return Err(VaultError::Unauthorized.into());
+        }
+
+        Ok(())
+    }
+
+    // Verifies that `vault` is the vault described by this state, using the stored canonical bump.
+    //
+    // This is a single `create_program_address` instead of the `find_program_address` bump search.
+    // Matching the stored creator and index also binds this state to that vault: only `Initialize`
+    // writes state accounts, at the canonical state PDA of the vault, so each vault has exactly one.
+    pub fn check_vault_address(&self, index: &[u8; 8], vault: &Pubkey) -> ProgramResult {
+        if self.index.ne(index) {
+            return Err(VaultError::InvalidStateAccount.into());
+        }
+
+        let vault_key = create_program_address(
+            &[b"vault", self.creator.as_ref(), index, &[self.bump]],
+            &crate::ID,
+        )?;
+        if vault.ne(&vault_key) {
+            return Err(VaultError::InvalidVaultAddress.into());
+        }
+
+        Ok(())
+    }
+
+    // Verifies that `state` is the state PDA of `vault` and returns its bump.
+    // Only needed by `Initialize`; later instructions rely on `check_vault`.
+    pub fn check_address(state: &AccountInfo, vault: &Pubkey) -> Result<u8, ProgramError> {
+        let (state_key, bump) = find_program_address(&[Self::SEED, vault.as_ref()], &crate::ID);
+        if state.key().ne(&state_key) {
+            return Err(VaultError::InvalidStateAddress.into());
+        }
+
+        Ok(bump)
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+
+    pub fn creator(&self) -> &Pubkey {
+        &self.creator
+    }
+
+    pub fn set_creator(&mut self, creator: &Pubkey) {
+        self.creator = *creator;
+    }
+
+    pub fn owner(&self) -> &Pubkey {
+        &self.owner
+    }
+
+    pub fn set_owner(&mut self, owner: &Pubkey) {
+        self.owner = *owner;
+    }
+
+    pub fn pending_owner(&self) -> &Pubkey {
+        &self.pending_owner
+    }
+
+    pub fn set_pending_owner(&mut self, pending_owner: &Pubkey) {
+        self.pending_owner = *pending_owner;
+    }
+
+    pub fn index(&self) -> u64 {
+        u64::from_le_bytes(self.index)
+    }
+
+    pub fn set_index(&mut self, index: u64) {
+        self.index = index.to_le_bytes();
+    }
+
+    pub fn beneficiary(&self) -> &Pubkey {
+        &self.beneficiary
+    }
+
+    pub fn set_beneficiary(&mut self, beneficiary: &Pubkey) {
+        self.beneficiary = *beneficiary;
+    }
+
+    pub fn unlock_ts(&self) -> i64 {
+        i64::from_le_bytes(self.unlock_ts)
+    }
+
+    pub fn set_unlock_ts(&mut self, unlock_ts: i64) {
+        self.unlock_ts = unlock_ts.to_le_bytes();
+    }
+
+    pub fn start_ts(&self) -> i64 {
+        i64::from_le_bytes(self.start_ts)
+    }
+
+    pub fn cliff_ts(&self) -> i64 {
+        i64::from_le_bytes(self.cliff_ts)
+    }
+
+    pub fn end_ts(&self) -> i64 {
+        i64::from_le_bytes(self.end_ts)
+    }
+
+    pub fn set_schedule(&mut self, start_ts: i64, cliff_ts: i64, end_ts: i64) {
+        self.start_ts = start_ts.to_le_bytes();
+        self.cliff_ts = cliff_ts.to_le_bytes();
+        self.end_ts = end_ts.to_le_bytes();
+    }
+
+    pub fn total_deposited(&self) -> u64 {
+        u64::from_le_bytes(self.total_deposited)
+    }
+
+    pub fn set_total_deposited(&mut self, total_deposited: u64) {
+        self.total_deposited = total_deposited.to_le_bytes();
+    }
+
+    pub fn total_withdrawn(&self) -> u64 {
+        u64::from_le_bytes(self.total_withdrawn)
+    }
+
+    pub fn set_total_withdrawn(&mut self, total_withdrawn: u64) {
+        self.total_withdrawn = total_withdrawn.to_le_bytes();
+    }
+
+    pub fn created_slot(&self) -> u64 {
+        u64::from_le_bytes(self.created_slot)
+    }
+
+    pub fn set_created_slot(&mut self, created_slot: u64) {
+        self.created_slot = created_slot.to_le_bytes();
+    }
+
+    pub fn label(&self) -> &[u8; 32] {
+        &self.label
+    }
+
+    pub fn set_label(&mut self, label: &[u8; 32]) {
+        self.label = *label;
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub fn members(&self) -> &[Pubkey] {
+        &self.members[..self.member_count as usize]
+    }
+
+    // Replaces the multisig configuration. `members` must already be validated
+    // (see `check_multisig`); unused slots are zeroed.
+    pub fn set_multisig(&mut self, threshold: u8, members: &[Pubkey]) {
+        self.threshold = threshold;
+        self.member_count = members.len() as u8;
+        self.members = [[0; 32]; Self::MAX_MEMBERS];
+        self.members[..members.len()].copy_from_slice(members);
+    }
+
+    // Whether `threshold` of `keys` is a consistent M-of-N configuration: none at all
+    // (`threshold == 0` and no keys), or between 1 and `max` distinct keys with `1 <= threshold <= keys`.
+    fn is_valid_quorum(threshold: u8, keys: &[Pubkey], max: usize) -> bool {
+        if threshold.eq(&0) {
+            return keys.is_empty();
+        }
+
+        if keys.len() > max || threshold as usize > keys.len() {
+            return false;
+        }
+
+        keys.iter()
+            .enumerate()
+            .all(|(i, key)| !keys[..i].contains(key))
+    }
+
+    // Number of distinct `keys` appearing as signers among `signers`.
+    fn count_signers(keys: &[Pubkey], signers: &[AccountInfo]) -> usize {
+        keys.iter()
+            .filter(|key| {
+                signers
+                    .iter()
+                    .any(|signer| signer.is_signer() && signer.key().eq(*key))
+            })
+            .count()
+    }
+
+    // Validates a multisig configuration, see `is_valid_quorum`.
+    pub fn check_multisig(threshold: u8, members: &[Pubkey]) -> ProgramResult {
+        if !Self::is_valid_quorum(threshold, members, Self::MAX_MEMBERS) {
+            return Err(VaultError::InvalidMultisig.into());
+        }
+
+        Ok(())
+    }
+
+    // Checks that the vault's authority approved the instruction.
+    //
+    // Without a multisig, the owner must sign. With one, at least `threshold` distinct members
+    // must appear as signers among `signers` (the trailing accounts of the instruction); the
+    // owner's signature alone is not enough. A member listed twice is only counted once.
+    pub fn check_authority(&self, owner: &AccountInfo, signers: &[AccountInfo]) -> ProgramResult {
+        if self.threshold.eq(&0) {
+            if !owner.is_signer() {
+                return Err(VaultError::NotSigner.into());
+            }
+            return Ok(());
+        }
+
+        if Self::count_signers(self.members(), signers) < self.threshold as usize {
+            return Err(VaultError::NotEnoughSigners.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn guardian_threshold(&self) -> u8 {
+        self.guardian_threshold
+    }
+
+    pub fn guardians(&self) -> &[Pubkey] {
+        &self.guardians[..self.guardian_count as usize]
+    }
+
+    pub fn challenge_period(&self) -> i64 {
+        i64::from_le_bytes(self.challenge_period)
+    }
+
+    // Replaces the guardians. `guardians` must already be validated (see `check_guardians`);
+    // unused slots are zeroed.
+    pub fn set_guardians(&mut self, threshold: u8, guardians: &[Pubkey], challenge_period: i64) {
+        self.guardian_threshold = threshold;
+        self.guardian_count = guardians.len() as u8;
+        self.guardians = [[0; 32]; Self::MAX_GUARDIANS];
+        self.guardians[..guardians.len()].copy_from_slice(guardians);
+        self.challenge_period = challenge_period.to_le_bytes();
+    }
+
+    // Validates a guardian configuration, see `is_valid_quorum`. The challenge period is in seconds
+    // and must be positive: with no period, a quorum of guardians could propose and execute a
+    // recovery in the same transaction, before the owner has any chance to cancel it. Removing the
+    // guardians (zero threshold) only needs a period that isn't negative.
+    pub fn check_guardians(
+        threshold: u8,
+        guardians: &[Pubkey],
+        challenge_period: i64,
+    ) -> ProgramResult {
+        if !Self::is_valid_quorum(threshold, guardians, Self::MAX_GUARDIANS)
+            || challenge_period < 0
+            || (threshold > 0 && challenge_period.eq(&0))
+        {
+            return Err(VaultError::InvalidGuardians.into());
+        }
+
+        Ok(())
+    }
+
+    // Checks that at least `guardian_threshold` distinct guardians appear as signers among `signers`.
+    pub fn check_guardian_quorum(&self, signers: &[AccountInfo]) -> ProgramResult {
+        if self.guardian_threshold.eq(&0) {
+            return Err(VaultError::InvalidGuardians.into());
+        }
+
+        if Self::count_signers(self.guardians(), signers) < self.guardian_threshold as usize {
+            return Err(VaultError::NotEnoughSigners.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn inactivity_period(&self) -> i64 {
+        i64::from_le_bytes(self.inactivity_period)
+    }
+
+    pub fn set_inactivity_period(&mut self, inactivity_period: i64) {
+        self.inactivity_period = inactivity_period.to_le_bytes();
+    }
+
+    pub fn heir(&self) -> &Pubkey {
+        &self.heir
+    }
+
+    pub fn set_heir(&mut self, heir: &Pubkey) {
+        self.heir = *heir;
+    }
+
+    pub fn last_active(&self) -> i64 {
+        i64::from_le_bytes(self.last_active)
+    }
+
+    // Records owner activity, restarting the inactivity period.
+    pub fn set_last_active(&mut self, now: i64) {
+        self.last_active = now.to_le_bytes();
+    }
+
+    // Checks that `heir` may claim the vault at `now`: it must be the vault's heir,
+    // the vault must have a dead-man switch, and the owner must have been inactive for the whole period.
+    // The owner's last action counts as a withdrawal request, so a claim also waits out the
+    // withdrawal delay; otherwise a compromised key could name itself heir with a tiny period.
+    pub fn check_claim(&self, heir: &Pubkey, now: i64) -> ProgramResult {
+        let inactivity_period = self.inactivity_period();
+        if inactivity_period.eq(&0) || self.heir.ne(heir) {
+            return Err(VaultError::Unauthorized.into());
+        }
+
+        let wait = inactivity_period.max(self.withdraw_delay(now));
+        if now < self.last_active().saturating_add(wait) {
+            return Err(VaultError::OwnerStillActive.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn open_accounts(&self) -> u32 {
+        u32::from_le_bytes(self.open_accounts)
+    }
+
+    // Counts an account created for this vault.
+    pub fn add_open_account(&mut self) {
+        self.open_accounts = self.open_accounts().saturating_add(1).to_le_bytes();
+    }
+
+    // Counts an account of this vault being closed.
+    pub fn remove_open_account(&mut self) {
+        self.open_accounts = self.open_accounts().saturating_sub(1).to_le_bytes();
+    }
+
+    pub fn freeze_authority(&self) -> &Pubkey {
+        &self.freeze_authority
+    }
+
+    pub fn set_freeze_authority(&mut self, freeze_authority: &Pubkey) {
+        self.freeze_authority = *freeze_authority;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.ne(&0)
+    }
+
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen as u8;
+    }
+
+    // Fails with `VaultFrozen` while the vault is frozen.
+    pub fn check_not_frozen(&self) -> ProgramResult {
+        if self.is_frozen() {
+            return Err(VaultError::VaultFrozen.into());
+        }
+
+        Ok(())
+    }
+
+    // Checks that `authority` is the vault's freeze authority and signed the transaction.
+    pub fn check_freeze_authority(&self, authority: &AccountInfo) -> ProgramResult {
+        if !authority.is_signer() {
+            return Err(VaultError::NotSigner.into());
+        }
+
+        if self.freeze_authority.eq(&[0; 32]) || self.freeze_authority.ne(authority.key()) {
+            return Err(VaultError::Unauthorized.into());
+        }
+
+        Ok(())
+    }
+
+    // Withdrawal delay in effect at `now`, taking a scheduled decrease into account.
+    pub fn withdraw_delay(&self, now: i64) -> i64 {
+        let pending_delay_ts = i64::from_le_bytes(self.pending_delay_ts);
+        if pending_delay_ts.ne(&0) && now >= pending_delay_ts {
+            return i64::from_le_bytes(self.pending_delay);
+        }
+
+        i64::from_le_bytes(self.withdraw_delay)
+    }
+
+    // Changes the withdrawal delay. A longer delay applies immediately; a shorter one only once
+    // the current delay has elapsed, exactly like a withdrawal requested now.
+    pub fn set_withdraw_delay(&mut self, delay: i64, now: i64) {
+        let current = self.withdraw_delay(now);
+        if delay >= current {
+            self.withdraw_delay = delay.to_le_bytes();
+            self.pending_delay = [0; 8];
+            self.pending_delay_ts = [0; 8];
+        } else {
+            self.withdraw_delay = current.to_le_bytes();
+            self.pending_delay = delay.to_le_bytes();
+            self.pending_delay_ts = now.saturating_add(current).to_le_bytes();
+        }
+    }
+
+    // Start of the window containing `clock` for the given period.
+    fn period_start(period: u8, clock: &Clock) -> u64 {
+        if period.eq(&Self::PERIOD_EPOCH) {
+            clock.epoch
+        } else {
+            (clock.unix_timestamp - clock.unix_timestamp.rem_euclid(Self::SECONDS_PER_DAY)) as u64
+        }
+    }
+
+    // The rate limit window as of `clock`: once the stored window is over, a new one starts with
+    // nothing spent, and any scheduled limit change takes effect.
+    pub fn rate_window(&self, clock: &Clock) -> RateWindow {
+        let mut window = RateWindow {
+            limit: u64::from_le_bytes(self.rate_limit),
+            period: self.rate_period,
+            start: u64::from_le_bytes(self.window_start),
+            spent: u64::from_le_bytes(self.window_spent),
+            next_applied: false,
+        };
+        if window.limit.eq(&0) || Self::period_start(window.period, clock).eq(&window.start) {
+            return window;
+        }
+
+        if self.rate_change_pending.ne(&0) {
+            window.limit = u64::from_le_bytes(self.next_rate_limit);
+            window.period = self.next_rate_period;
+            window.next_applied = true;
+        }
+        window.start = Self::period_start(window.period, clock);
+        window.spent = 0;
+        window
+    }
+
+    fn set_rate_window(&mut self, window: &RateWindow) {
+        self.rate_limit = window.limit.to_le_bytes();
+        self.rate_period = window.period;
+        self.window_start = window.start.to_le_bytes();
+        self.window_spent = window.spent.to_le_bytes();
+        if window.next_applied {
+            self.rate_change_pending = 0;
+        }
+    }
+
+    // Changes the rate limit. A stricter limit (a first limit, or a lower one over the same period)
+    // applies immediately; anything else, including removing the limit with `limit == 0`, only
+    // from the next window on.
+    pub fn set_rate_limit(&mut self, limit: u64, period: u8, clock: &Clock) {
+        let mut window = self.rate_window(clock);
+        self.set_rate_window(&window);
+
+        let stricter = window.limit.eq(&0)
+            || (limit.ne(&0) && period.eq(&window.period) && limit <= window.limit);
+        if stricter {
+            if window.limit.eq(&0) {
+                window.start = Self::period_start(period, clock);
+                window.spent = 0;
+            }
+            window.limit = limit;
+            window.period = period;
+            self.set_rate_window(&window);
+            self.rate_change_pending = 0;
+        } else {
+            self.next_rate_limit = limit.to_le_bytes();
+            self.next_rate_period = period;
+            self.rate_change_pending = 1;
+        }
+    }
+
+    // Fails with `RateLimitExceeded` if withdrawing `amount` now would go over the rate limit.
+    pub fn check_rate_limit(&self, amount: u64, clock: &Clock) -> ProgramResult {
+        let window = self.rate_window(clock);
+        if window.limit.ne(&0) && window.spent.saturating_add(amount) > window.limit {
+            return Err(VaultError::RateLimitExceeded.into());
+        }
+
+        Ok(())
+    }
+
+    // Counts `amount` against the current window (`check_rate_limit` guaranteed it fits).
+    pub fn record_rate_limited(&mut self, amount: u64, clock: &Clock) {
+        let mut window = self.rate_window(clock);
+        if window.limit.ne(&0) {
+            window.spent += amount;
+        }
+        self.set_rate_window(&window);
+    }
+
+    pub fn has_allowlist(&self) -> bool {
+        self.has_allowlist.ne(&0)
+    }
+
+    pub fn set_has_allowlist(&mut self) {
+        self.has_allowlist = 1;
+    }
+
+    // Splits the allowlist off the remaining accounts of a withdrawal, for vaults that have one.
+    pub fn split_allowlist<'a>(
+        &self,
+        remaining: &'a [AccountInfo],
+    ) -> Result<(Option<&'a AccountInfo>, &'a [AccountInfo]), ProgramError> {
+        if !self.has_allowlist() {
+            return Ok((None, remaining));
+        }
+
+        let Some((allowlist, remaining)) = remaining.split_first() else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Ok((Some(allowlist), remaining))
+    }
+
+    // Fails with `VaultLocked` while the unlock timestamp is in the future.
+    pub fn check_unlocked(&self, now: i64) -> ProgramResult {
+        if now < self.unlock_ts() {
+            return Err(VaultError::VaultLocked.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn has_schedule(&self) -> bool {
+        self.end_ts().ne(&0)
+    }
+
+    // Amount of `total_deposited` released by the schedule at `now`.
+    // Nothing is released before the cliff (or the start, without a cliff), everything after the end,
+    // and in between the release is linear from `start_ts`.
+    // Vaults without a schedule are fully vested.
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        let total = self.total_deposited();
+        if !self.has_schedule() {
+            return total;
+        }
+
+        let (start, end) = (self.start_ts(), self.end_ts());
+        if now < start.max(self.cliff_ts()) {
+            return 0;
+        }
+        if now >= end {
+            return total;
+        }
+
+        // `start <= now < end` here, so both differences are positive and the result is below `total`.
+        let elapsed = (now - start) as u128;
+        let duration = (end - start) as u128;
+        (total as u128 * elapsed / duration) as u64
+    }
+
+    // Vested lamports that have not been withdrawn yet.
+    pub fn withdrawable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now)
+            .saturating_sub(self.total_withdrawn())
+    }
+}
@@ -0,0 +1,229 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+use pinocchio_token::state::{Mint, TokenAccount};
+
+use crate::VaultError;
+
+// Minimal SPL Token helpers shared by the token instructions.
+// Both the legacy Token program and Token-2022 use the same base layout for mints and
+// token accounts (Token-2022 only appends extensions after it), so the base state types of
+// pinocchio-token read both.
+
+// TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA
+pub const TOKEN_PROGRAM_ID: Pubkey = pinocchio_token::ID;
+
+// TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pinocchio_token_2022::ID;
+
+// ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = [
+    0x8c, 0x97, 0x25, 0x8f, 0x4e, 0x24, 0x89, 0xf1, 0xbb, 0x3d, 0x10, 0x29, 0x14, 0x8e, 0x0d, 0x83,
+    0x0b, 0x5a, 0x13, 0x99, 0xda, 0xff, 0x10, 0x84, 0x04, 0x8e, 0x7b, 0xd8, 0xdb, 0xe9, 0xf8, 0x59,
+];
+
+// Only the legacy Token program and Token-2022 are accepted.
+pub fn check_token_program(token_program: &AccountInfo) -> Result<(), ProgramError> {
+    let key = token_program.key();
+    if key.ne(&TOKEN_PROGRAM_ID) && key.ne(&TOKEN_2022_PROGRAM_ID) {
+        return Err(VaultError::InvalidTokenProgram.into());
+    }
+
+    Ok(())
+}
+
+// Validates the mint against the token program and returns its decimals,
+// which `TransferChecked` needs.
+pub fn mint_decimals(mint: &AccountInfo, token_program: &Pubkey) -> Result<u8, ProgramError> {
+    if !mint.is_owned_by(token_program) {
+        return Err(VaultError::InvalidMint.into());
+    }
+
+    let data = mint.try_borrow_data()?;
+    if data.len() < Mint::LEN {
+        return Err(VaultError::InvalidMint.into());
+    }
+    // SAFETY: The data holds at least a base mint, which Token-2022 extensions only follow.
+    let mint = unsafe { Mint::from_bytes_unchecked(&data[..Mint::LEN]) };
+    if !mint.is_initialized() {
+        return Err(VaultError::InvalidMint.into());
+    }
+
+    Ok(mint.decimals())
+}
+
+// Validates a token account's program, mint and authority, and returns its balance.
+pub fn token_account_amount(
+    token_account: &AccountInfo,
+    token_program: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+) -> Result<u64, ProgramError> {
+    let amount = token_account_balance(token_account, token_program, authority)?;
+
+    let data = token_account.try_borrow_data()?;
+    // SAFETY: `token_account_balance` checked that the data holds a base token account.
+    if unsafe { TokenAccount::from_bytes_unchecked(&data[..TokenAccount::LEN]) }
+        .mint()
+        .ne(mint)
+    {
+        return Err(VaultError::InvalidTokenAccount.into());
+    }
+
+    Ok(amount)
+}
+
+// Validates a token account's program and authority, whatever its mint, and returns its balance.
+pub fn token_account_balance(
+    token_account: &AccountInfo,
+    token_program: &Pubkey,
+    authority: &Pubkey,
+) -> Result<u64, ProgramError> {
+    if !token_account.is_owned_by(token_program) {
+        return Err(VaultError::InvalidTokenAccount.into());
+    }
+
+    let data = token_account.try_borrow_data()?;
+    if data.len() < TokenAccount::LEN {
+        return Err(VaultError::InvalidTokenAccount.into());
+    }
+    // SAFETY: The data holds at least a base token account, which Token-2022 extensions only follow.
+    let account = unsafe { TokenAccount::from_bytes_unchecked(&data[..TokenAccount::LEN]) };
+    if !account.is_initialized() || account.owner().ne(authority) {
+        return Err(VaultError::InvalidTokenAccount.into());
+    }
+
+    Ok(account.amount())
+}
+
+// Derives the associated token account address of `wallet` for `mint` under `token_program`.
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Transfer tokens, checking the mint and its decimals.
+///
+/// Works for both the legacy Token program and Token-2022, through the `TransferChecked`
+/// instruction of pinocchio-token or pinocchio-token-2022. For Token-2022 mints with the
+/// transfer-fee extension, the fee is withheld in the destination account, so the
+/// destination is credited `amount - fee`.
+///
+/// ### Accounts:
+///   0. `[WRITE]` Source account
+///   1. `[]` Mint account
+///   2. `[WRITE]` Destination account
+///   3. `[SIGNER]` Source account's authority
+pub struct TransferChecked<'a> {
+    /// Source account.
+    pub from: &'a AccountInfo,
+
+    /// Mint account.
+    pub mint: &'a AccountInfo,
+
+    /// Destination account.
+    pub to: &'a AccountInfo,
+
+    /// Source account's authority.
+    pub authority: &'a AccountInfo,
+
+    /// Token program that owns the mint and both token accounts.
+    pub token_program: &'a Pubkey,
+
+    /// Amount of tokens to transfer.
+    pub amount: u64,
+
+    /// Decimals of the mint.
+    pub decimals: u8,
+}
+
+impl TransferChecked<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.token_program.eq(&TOKEN_2022_PROGRAM_ID) {
+            pinocchio_token_2022::instructions::TransferChecked {
+                from: self.from,
+                mint: self.mint,
+                to: self.to,
+                authority: self.authority,
+                amount: self.amount,
+                decimals: self.decimals,
+                token_program: self.token_program,
+            }
+            .invoke_signed(signers)
+        } else {
+            pinocchio_token::instructions::TransferChecked {
+                from: self.from,
+                mint: self.mint,
+                to: self.to,
+                authority: self.authority,
+                amount: self.amount,
+                decimals: self.decimals,
+            }
+            .invoke_signed(signers)
+        }
+    }
+}
+
+/// Close an empty token account, sending its rent to the destination.
+///
+/// Works for both the legacy Token program and Token-2022, through the `CloseAccount`
+/// instruction of pinocchio-token or pinocchio-token-2022. The token program rejects accounts
+/// that still hold tokens (or, for Token-2022, withheld transfer fees).
+///
+/// ### Accounts:
+///   0. `[WRITE]` Account to close
+///   1. `[WRITE]` Destination account
+///   2. `[SIGNER]` Account's owner
+pub struct CloseAccount<'a> {
+    /// Token account to close.
+    pub account: &'a AccountInfo,
+
+    /// Destination of the account's lamports.
+    pub destination: &'a AccountInfo,
+
+    /// Token account's owner.
+    pub authority: &'a AccountInfo,
+
+    /// Token program that owns the token account.
+    pub token_program: &'a Pubkey,
+}
+
+impl CloseAccount<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    #[inline(always)]
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        if self.token_program.eq(&TOKEN_2022_PROGRAM_ID) {
+            pinocchio_token_2022::instructions::CloseAccount {
+                account: self.account,
+                destination: self.destination,
+                authority: self.authority,
+                token_program: self.token_program,
+            }
+            .invoke_signed(signers)
+        } else {
+            pinocchio_token::instructions::CloseAccount {
+                account: self.account,
+                destination: self.destination,
+                authority: self.authority,
+            }
+            .invoke_signed(signers)
+        }
+    }
+}
@@ -0,0 +1,622 @@
+// Compute units consumed by every instruction, checked against a stored baseline.
+//
+//   cargo bench                        # fails if any instruction uses more CUs than its baseline
+//   CU_TOLERANCE=50 cargo bench        # allows up to 50 CUs over the baseline
+//   CU_TOLERANCE=2% cargo bench        # allows up to 2% over the baseline
+//   UPDATE_BASELINE=1 cargo bench      # records the current numbers as the new baseline
+//
+// Instructions without a baseline row fail too, so a new instruction can't go unmeasured.
+// Without a baseline file at all (a fresh checkout before the first baseline was committed), the
+// run records one instead of failing every instruction; commit it.
+// Every run writes its table to `target/compute_units.md`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use std::{collections::HashMap, fs, process};
+
+use blueshift_vault::{client, token::TOKEN_PROGRAM_ID};
+use common::*;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+const BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/benches/compute_units.md");
+const OUTPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target/compute_units.md");
+
+const BALANCE: u64 = 1_000_000_000;
+
+// Allowed increase over the baseline when `CU_TOLERANCE` isn't set.
+const DEFAULT_TOLERANCE: &str = "1%";
+
+// Sets up a fresh vault and returns the compute units of the instruction being measured.
+type Case = fn() -> u64;
+
+// One case per instruction, in discriminator order.
+const CASES: &[(&str, Case)] = &[
+    ("Deposit", deposit),
+    ("Withdraw", withdraw),
+    ("LegacyWithdraw", legacy_withdraw),
+    ("DepositToken", deposit_token),
+    ("WithdrawToken", withdraw_token),
+    ("Initialize", initialize),
+    ("GetVested", get_vested),
+    ("WithdrawTo", withdraw_to),
+    ("Approve", approve),
+    ("Revoke", revoke),
+    ("DelegatedWithdraw", delegated_withdraw),
+    ("SetMultisig", set_multisig),
+    ("RequestWithdraw", request_withdraw),
+    ("ExecuteWithdraw", execute_withdraw),
+    ("CancelWithdraw", cancel_withdraw),
+    ("SetWithdrawDelay", set_withdraw_delay),
+    ("SetRateLimit", set_rate_limit),
+    ("AddDestination", add_destination),
+    ("RemoveDestination", remove_destination),
+    ("TransferOwnership", transfer_ownership),
+    ("AcceptOwnership", accept_ownership),
+    ("SetGuardians", set_guardians),
+    ("ProposeRecovery", propose_recovery),
+    ("CancelRecovery", cancel_recovery),
+    ("ExecuteRecovery", execute_recovery),
+    ("Heartbeat", heartbeat),
+    ("SetInheritance", set_inheritance),
+    ("Claim", claim),
+    ("Close", close),
+    ("SetFreezeAuthority", set_freeze_authority),
+    ("Freeze", freeze),
+    ("Thaw", thaw),
+];
+
+fn main() {
+    let baseline = read_baseline();
+    let record = baseline.is_none() || std::env::var_os("UPDATE_BASELINE").is_some();
+    let baseline = baseline.unwrap_or_default();
+    let tolerance = std::env::var("CU_TOLERANCE").unwrap_or_else(|_| DEFAULT_TOLERANCE.into());
+    let Some(tolerance) = Tolerance::parse(&tolerance) else {
+        eprintln!("Invalid CU_TOLERANCE {tolerance:?}, expected a number of CUs or a percentage");
+        process::exit(1);
+    };
+
+    let mut table =
+        String::from("| Instruction | Compute units | Baseline | Change |\n|---|---:|---:|---:|\n");
+    let mut current = String::from("| Instruction | Compute units |\n|---|---:|\n");
+    let mut regressions = Vec::new();
+    for (name, case) in CASES {
+        let units = case();
+        current.push_str(&format!("| {name} | {units} |\n"));
+
+        match baseline.get(*name) {
+            Some(&base) => {
+                let change = units as i64 - base as i64;
+                table.push_str(&format!("| {name} | {units} | {base} | {change:+} |\n"));
+                if units > tolerance.allowed(base) {
+                    regressions.push(format!("{name}: {units} CUs, baseline {base} ({change:+})"));
+                }
+            }
+            None => {
+                table.push_str(&format!("| {name} | {units} | - | new |\n"));
+                regressions.push(format!("{name}: {units} CUs, no baseline"));
+            }
+        }
+    }
+
+    print!("{table}");
+    fs::create_dir_all(concat!(env!("CARGO_MANIFEST_DIR"), "/target")).unwrap();
+    fs::write(OUTPUT, &table).unwrap();
+
+    if record {
+        fs::write(BASELINE, &current).unwrap();
+        println!("\nBaseline updated: {BASELINE}");
+        return;
+    }
+
+    if !regressions.is_empty() {
+        eprintln!("\nCompute unit regressions (tolerance {tolerance}):");
+        for regression in &regressions {
+            eprintln!("  {regression}");
+        }
+        eprintln!("\nIf intended, record a new baseline with `UPDATE_BASELINE=1 cargo bench`.");
+        process::exit(1);
+    }
+}
+
+// How far above its baseline an instruction may go before the run fails.
+#[derive(Clone, Copy)]
+enum Tolerance {
+    Units(u64),
+    Percent(u64),
+}
+
+impl Tolerance {
+    // `"50"` allows 50 CUs, `"2%"` allows 2% of the baseline.
+    fn parse(tolerance: &str) -> Option<Self> {
+        let tolerance = tolerance.trim();
+        match tolerance.strip_suffix('%') {
+            Some(percent) => percent.trim().parse().ok().map(Self::Percent),
+            None => tolerance.parse().ok().map(Self::Units),
+        }
+    }
+
+    // Highest number of compute units accepted for an instruction with baseline `base`.
+    fn allowed(self, base: u64) -> u64 {
+        match self {
+            Self::Units(units) => base.saturating_add(units),
+            Self::Percent(percent) => base.saturating_add(base.saturating_mul(percent) / 100),
+        }
+    }
+}
+
+impl std::fmt::Display for Tolerance {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Units(units) => write!(f, "{units} CUs"),
+            Self::Percent(percent) => write!(f, "{percent}%"),
+        }
+    }
+}
+
+// Parses the `| Instruction | Compute units |` rows of the baseline table, `None` without a
+// baseline file.
+fn read_baseline() -> Option<HashMap<String, u64>> {
+    let baseline = fs::read_to_string(BASELINE).ok()?;
+
+    let rows = baseline
+        .lines()
+        .filter_map(|line| {
+            let mut cells = line
+                .split('|')
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty());
+            let name = cells.next()?;
+            let units = cells.next()?.parse().ok()?;
+            Some((name.to_string(), units))
+        })
+        .collect();
+    Some(rows)
+}
+
+// SOL
+
+fn deposit() -> u64 {
+    let mut env = Env::initialized();
+    env.process(&env.deposit(BALANCE))
+}
+
+fn withdraw() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.withdraw(None))
+}
+
+fn legacy_withdraw() -> u64 {
+    let mut env = Env::new();
+    env.fund(&legacy_vault_address(&env.owner), BALANCE);
+    env.process(&to_sdk(client::legacy_withdraw(&env.key())))
+}
+
+fn initialize() -> u64 {
+    let mut env = Env::new();
+    env.process(&env.initialize_instruction(INDEX, 0))
+}
+
+fn get_vested() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::get_vested(&env.key(), &env.key(), INDEX)))
+}
+
+fn withdraw_to() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::withdraw_to(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &Pubkey::new_unique().to_bytes(),
+        None,
+    )))
+}
+
+// SPL tokens
+
+// A funded vault, plus a mint and token accounts for the owner and the vault.
+// Returns the mint and the owner's token account.
+fn token_env(owner_amount: u64, vault_amount: u64) -> (Env, Pubkey, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let (mint, owner_token_account) = env.add_tokens(owner_amount, vault_amount);
+    (env, mint, owner_token_account)
+}
+
+fn deposit_token() -> u64 {
+    let (mut env, mint, owner_token_account) = token_env(1_000, 0);
+    env.process(&to_sdk(client::deposit_token(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &mint.to_bytes(),
+        &owner_token_account.to_bytes(),
+        &TOKEN_PROGRAM_ID,
+        1_000,
+    )))
+}
+
+fn withdraw_token() -> u64 {
+    let (mut env, mint, owner_token_account) = token_env(0, 1_000);
+    env.process(&to_sdk(client::withdraw_token(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &mint.to_bytes(),
+        &owner_token_account.to_bytes(),
+        &TOKEN_PROGRAM_ID,
+        None,
+    )))
+}
+
+// Delegates
+
+fn approve_instruction(env: &Env, delegate: &Pubkey) -> Instruction {
+    to_sdk(client::approve(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &delegate.to_bytes(),
+        BALANCE,
+        0,
+        0,
+    ))
+}
+
+fn approve() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&approve_instruction(&env, &Pubkey::new_unique()))
+}
+
+fn revoke() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    let delegate = Pubkey::new_unique();
+    env.process(&approve_instruction(&env, &delegate));
+
+    env.process(&to_sdk(client::revoke(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &delegate.to_bytes(),
+    )))
+}
+
+fn delegated_withdraw() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    let delegate = Pubkey::new_unique();
+    env.process(&approve_instruction(&env, &delegate));
+
+    env.process(&to_sdk(client::delegated_withdraw(
+        &delegate.to_bytes(),
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &Pubkey::new_unique().to_bytes(),
+        Some(100_000_000),
+    )))
+}
+
+// Configuration
+
+fn set_multisig() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    let members = [
+        Pubkey::new_unique().to_bytes(),
+        Pubkey::new_unique().to_bytes(),
+    ];
+    env.process(&to_sdk(client::set_multisig(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        2,
+        &members,
+    )))
+}
+
+fn set_withdraw_delay() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::set_withdraw_delay(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        60,
+    )))
+}
+
+fn set_rate_limit() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::set_rate_limit(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        BALANCE,
+        blueshift_vault::VaultState::PERIOD_DAY,
+    )))
+}
+
+fn heartbeat() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::heartbeat(&env.key(), &env.key(), INDEX)))
+}
+
+fn set_freeze_authority() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::set_freeze_authority(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &Pubkey::new_unique().to_bytes(),
+    )))
+}
+
+// Withdrawal queue
+
+fn request_instruction(env: &Env, destination: &Pubkey, request_id: u64) -> Instruction {
+    to_sdk(client::request_withdraw(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &destination.to_bytes(),
+        request_id,
+        100_000_000,
+    ))
+}
+
+fn queued_env() -> Env {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::set_withdraw_delay(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        60,
+    )));
+    env
+}
+
+fn request_withdraw() -> u64 {
+    let mut env = queued_env();
+    env.process(&request_instruction(&env, &Pubkey::new_unique(), 1))
+}
+
+fn execute_withdraw() -> u64 {
+    let mut env = queued_env();
+    let destination = Pubkey::new_unique();
+    env.process(&request_instruction(&env, &destination, 1));
+    env.warp(60);
+
+    env.process(&to_sdk(client::execute_withdraw(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        1,
+        &destination.to_bytes(),
+    )))
+}
+
+fn cancel_withdraw() -> u64 {
+    let mut env = queued_env();
+    env.process(&request_instruction(&env, &Pubkey::new_unique(), 1));
+
+    env.process(&to_sdk(client::cancel_withdraw(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        1,
+    )))
+}
+
+// Allowlist
+
+fn add_destination_instruction(env: &Env, destination: &Pubkey) -> Instruction {
+    to_sdk(client::add_destination(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &destination.to_bytes(),
+    ))
+}
+
+fn add_destination() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&add_destination_instruction(&env, &Pubkey::new_unique()))
+}
+
+fn remove_destination() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    let destination = Pubkey::new_unique();
+    env.process(&add_destination_instruction(&env, &destination));
+
+    env.process(&to_sdk(client::remove_destination(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &destination.to_bytes(),
+    )))
+}
+
+// Ownership
+
+fn transfer_ownership_instruction(env: &Env, new_owner: &Pubkey) -> Instruction {
+    to_sdk(client::transfer_ownership(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &new_owner.to_bytes(),
+    ))
+}
+
+fn transfer_ownership() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&transfer_ownership_instruction(&env, &Pubkey::new_unique()))
+}
+
+fn accept_ownership() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    let new_owner = Pubkey::new_unique();
+    env.process(&transfer_ownership_instruction(&env, &new_owner));
+
+    env.process(&to_sdk(client::accept_ownership(
+        &new_owner.to_bytes(),
+        &env.key(),
+        INDEX,
+    )))
+}
+
+// Social recovery
+
+// A vault with two guardians (both needed) and a 60-second challenge period.
+fn guarded_env() -> (Env, [[u8; 32]; 2]) {
+    let mut env = Env::funded(BALANCE);
+    let guardians = [
+        Pubkey::new_unique().to_bytes(),
+        Pubkey::new_unique().to_bytes(),
+    ];
+    env.process(&set_guardians_instruction(&env, &guardians));
+    (env, guardians)
+}
+
+fn set_guardians_instruction(env: &Env, guardians: &[[u8; 32]; 2]) -> Instruction {
+    to_sdk(client::set_guardians(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        2,
+        60,
+        guardians,
+    ))
+}
+
+// Opens a proposal paid by a new key, which is returned.
+fn propose_instruction(env: &mut Env, guardians: &[[u8; 32]; 2]) -> (Instruction, Pubkey) {
+    let payer = Pubkey::new_unique();
+    env.fund(&payer, OWNER_LAMPORTS);
+
+    let instruction = to_sdk(client::propose_recovery(
+        &payer.to_bytes(),
+        &env.key(),
+        INDEX,
+        &Pubkey::new_unique().to_bytes(),
+        guardians,
+    ));
+    (instruction, payer)
+}
+
+fn set_guardians() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    let guardians = [
+        Pubkey::new_unique().to_bytes(),
+        Pubkey::new_unique().to_bytes(),
+    ];
+    env.process(&set_guardians_instruction(&env, &guardians))
+}
+
+fn propose_recovery() -> u64 {
+    let (mut env, guardians) = guarded_env();
+    let (instruction, _) = propose_instruction(&mut env, &guardians);
+    env.process(&instruction)
+}
+
+fn cancel_recovery() -> u64 {
+    let (mut env, guardians) = guarded_env();
+    let (instruction, payer) = propose_instruction(&mut env, &guardians);
+    env.process(&instruction);
+
+    env.process(&to_sdk(client::cancel_recovery(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &payer.to_bytes(),
+    )))
+}
+
+fn execute_recovery() -> u64 {
+    let (mut env, guardians) = guarded_env();
+    let (instruction, payer) = propose_instruction(&mut env, &guardians);
+    env.process(&instruction);
+    env.warp(60);
+
+    env.process(&to_sdk(client::execute_recovery(
+        &env.key(),
+        INDEX,
+        &payer.to_bytes(),
+    )))
+}
+
+// Inheritance
+
+fn set_inheritance_instruction(env: &Env, heir: &Pubkey) -> Instruction {
+    to_sdk(client::set_inheritance(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &heir.to_bytes(),
+        60,
+    ))
+}
+
+fn set_inheritance() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&set_inheritance_instruction(&env, &Pubkey::new_unique()))
+}
+
+fn claim() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    let heir = Pubkey::new_unique();
+    env.process(&set_inheritance_instruction(&env, &heir));
+    env.warp(60);
+
+    env.process(&to_sdk(client::claim(
+        &heir.to_bytes(),
+        &env.key(),
+        &env.key(),
+        INDEX,
+        None,
+    )))
+}
+
+// Teardown
+
+fn close() -> u64 {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::close(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &[],
+        &[],
+    )))
+}
+
+// Freeze
+
+// A vault whose freeze authority is returned.
+fn freezable_env() -> (Env, [u8; 32]) {
+    let mut env = Env::funded(BALANCE);
+    let freeze_authority = Pubkey::new_unique().to_bytes();
+    env.process(&to_sdk(client::set_freeze_authority(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &freeze_authority,
+    )));
+    (env, freeze_authority)
+}
+
+fn freeze() -> u64 {
+    let (mut env, freeze_authority) = freezable_env();
+    env.process(&to_sdk(client::freeze(
+        &freeze_authority,
+        &env.key(),
+        INDEX,
+    )))
+}
+
+fn thaw() -> u64 {
+    let (mut env, freeze_authority) = freezable_env();
+    env.process(&to_sdk(client::freeze(
+        &freeze_authority,
+        &env.key(),
+        INDEX,
+    )));
+    env.process(&to_sdk(client::thaw(&freeze_authority, &env.key(), INDEX)))
+}
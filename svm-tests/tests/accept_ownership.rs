@@ -0,0 +1,108 @@
+mod common;
+
+use blueshift_vault::{client, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+
+// A funded vault proposed to the returned new owner.
+fn transfer_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let new_owner = Pubkey::new_unique();
+    env.fund(&new_owner, OWNER_LAMPORTS);
+    env.process(&env.transfer_ownership(&new_owner));
+    (env, new_owner)
+}
+
+#[test]
+fn accept_hands_the_vault_over_in_place() {
+    let (mut env, new_owner) = transfer_env();
+    let vault = env.vault;
+
+    env.process(&env.accept_ownership(&new_owner));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.owner(), &new_owner.to_bytes());
+    assert_eq!(state.pending_owner(), &[0; 32]);
+    // The vault keeps the creator's address, so the funds never moved.
+    assert_eq!(state.creator(), &env.key());
+    assert_eq!(env.lamports(&vault), BALANCE);
+
+    // The previous owner lost the vault, the new one can withdraw from it.
+    env.expect_err(&env.withdraw(None), vault_error(VaultError::Unauthorized));
+    let new_owner_before = env.lamports(&new_owner);
+    env.process(&to_sdk(client::withdraw(
+        &new_owner.to_bytes(),
+        &env.key(),
+        INDEX,
+        None,
+    )));
+
+    assert_eq!(env.lamports(&new_owner), new_owner_before + BALANCE);
+    assert_eq!(env.lamports(&vault), 0);
+}
+
+#[test]
+fn rejects_accepting_twice() {
+    let (mut env, new_owner) = transfer_env();
+    env.process(&env.accept_ownership(&new_owner));
+
+    env.expect_err(
+        &env.accept_ownership(&new_owner),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+// AcceptOwnershipAccounts
+
+#[test]
+fn rejects_without_a_proposal() {
+    let mut env = Env::initialized();
+
+    env.expect_err(
+        &env.accept_ownership(&Pubkey::new_unique()),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn rejects_signer_not_the_proposed_owner() {
+    let (mut env, _) = transfer_env();
+
+    env.expect_err(
+        &env.accept_ownership(&Pubkey::new_unique()),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn rejects_new_owner_not_signer() {
+    // Otherwise the vault could be handed to a key nobody controls.
+    let (mut env, new_owner) = transfer_env();
+    let mut instruction = env.accept_ownership(&new_owner);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_wrong_vault_address() {
+    let (mut env, new_owner) = transfer_env();
+    let mut instruction = env.accept_ownership(&new_owner);
+    instruction.accounts[1].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+// AcceptOwnershipInstructionData
+
+#[test]
+fn rejects_malformed_data() {
+    let (mut env, new_owner) = transfer_env();
+    let mut instruction = env.accept_ownership(&new_owner);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use blueshift_vault::{Allowlist, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+
+#[test]
+fn first_addition_creates_the_allowlist() {
+    let mut env = Env::funded(BALANCE);
+    let destination = Pubkey::new_unique();
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&env.add_destination(&destination));
+
+    let allowlist_key = allowlist_address(&env.vault);
+    let rent = env.rent_exempt(Allowlist::LEN);
+    assert_eq!(env.lamports(&allowlist_key), rent);
+    assert_eq!(env.lamports(&env.owner), owner_before - rent);
+
+    let account = env.account(&allowlist_key);
+    let allowlist = Allowlist::load(&account.data).unwrap();
+    assert_eq!(allowlist.vault(), &env.vault.to_bytes());
+    assert_eq!(allowlist.entries().len(), 1);
+    assert_eq!(
+        allowlist.entries()[0].destination(),
+        &destination.to_bytes()
+    );
+    assert_eq!(
+        allowlist.entries()[0].active_ts(),
+        NOW + Allowlist::ADD_DELAY
+    );
+
+    let account = env.account(&env.state);
+    assert!(VaultState::load(&account.data).unwrap().has_allowlist());
+    assert_eq!(env.open_accounts(), 1);
+}
+
+#[test]
+fn destination_is_allowed_after_the_timelock() {
+    let mut env = Env::funded(BALANCE);
+    let destination = Pubkey::new_unique();
+    env.process(&env.add_destination(&destination));
+
+    env.warp(Allowlist::ADD_DELAY - 1);
+    env.expect_err(
+        &env.withdraw_to_allowed(&destination, None),
+        vault_error(VaultError::DestinationNotAllowed),
+    );
+
+    env.warp(1);
+    env.process(&env.withdraw_to_allowed(&destination, None));
+
+    assert_eq!(env.lamports(&destination), BALANCE);
+}
+
+#[test]
+fn rejects_destination_not_listed() {
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.add_destination(&Pubkey::new_unique()));
+    env.warp(Allowlist::ADD_DELAY);
+
+    env.expect_err(
+        &env.withdraw_to_allowed(&Pubkey::new_unique(), None),
+        vault_error(VaultError::DestinationNotAllowed),
+    );
+}
+
+#[test]
+fn rejects_destination_already_listed() {
+    // Adding it again doesn't restart its timelock either.
+    let mut env = Env::funded(BALANCE);
+    let destination = Pubkey::new_unique();
+    env.process(&env.add_destination(&destination));
+
+    env.expect_err(
+        &env.add_destination(&destination),
+        vault_error(VaultError::InvalidAllowlist),
+    );
+}
+
+#[test]
+fn holds_sixteen_destinations() {
+    let mut env = Env::initialized();
+    for _ in 0..Allowlist::MAX_ENTRIES {
+        env.process(&env.add_destination(&Pubkey::new_unique()));
+    }
+
+    env.expect_err(
+        &env.add_destination(&Pubkey::new_unique()),
+        vault_error(VaultError::AllowlistFull),
+    );
+}
+
+// AddDestinationAccounts
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = env.add_destination(&Pubkey::new_unique());
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::initialized();
+    let intruder = Pubkey::new_unique();
+    env.fund(&intruder, OWNER_LAMPORTS);
+    let mut instruction = env.add_destination(&intruder);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_allowlist_of_another_vault() {
+    let mut env = Env::initialized();
+    let mut instruction = env.add_destination(&Pubkey::new_unique());
+    instruction.accounts[3].pubkey = allowlist_address(&vault_address(&env.owner, INDEX + 1));
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidAllowlist));
+}
+
+// AddDestinationInstructionData
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.add_destination(&Pubkey::new_unique());
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
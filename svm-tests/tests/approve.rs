@@ -0,0 +1,101 @@
+mod common;
+
+use blueshift_vault::{DelegateRecord, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+#[test]
+fn approve_creates_the_delegate_record() {
+    let mut env = Env::initialized();
+    let delegate = Pubkey::new_unique();
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&env.approve(&delegate, 500, 1_000, 100));
+
+    let record_key = delegate_record_address(&env.vault, &delegate);
+    let rent = env.rent_exempt(DelegateRecord::LEN);
+    assert_eq!(env.lamports(&record_key), rent);
+    assert_eq!(env.lamports(&env.owner), owner_before - rent);
+
+    let account = env.account(&record_key);
+    let record = DelegateRecord::load(&account.data).unwrap();
+    assert_eq!(record.vault(), &env.vault.to_bytes());
+    assert_eq!(record.delegate(), &delegate.to_bytes());
+    assert_eq!(record.allowance(), 500);
+    assert_eq!(record.expiry_slot(), 1_000);
+    assert_eq!(record.per_tx_cap(), 100);
+
+    let account = env.account(&env.state);
+    assert_eq!(VaultState::load(&account.data).unwrap().open_accounts(), 1);
+}
+
+#[test]
+fn approve_again_overwrites_the_allowance() {
+    let mut env = Env::initialized();
+    let delegate = Pubkey::new_unique();
+    env.process(&env.approve(&delegate, 500, 1_000, 100));
+
+    env.process(&env.approve(&delegate, 50, 0, 0));
+
+    let account = env.account(&delegate_record_address(&env.vault, &delegate));
+    let record = DelegateRecord::load(&account.data).unwrap();
+    assert_eq!(record.allowance(), 50);
+    assert_eq!(record.expiry_slot(), 0);
+    assert_eq!(record.per_tx_cap(), 0);
+
+    // The record already existed, so it isn't counted twice.
+    let account = env.account(&env.state);
+    assert_eq!(VaultState::load(&account.data).unwrap().open_accounts(), 1);
+}
+
+// ApproveAccounts
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = env.approve(&Pubkey::new_unique(), 500, 0, 0);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::initialized();
+    let intruder = Pubkey::new_unique();
+    env.fund(&intruder, OWNER_LAMPORTS);
+    let mut instruction = env.approve(&intruder, 500, 0, 0);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_record_of_another_delegate() {
+    let mut env = Env::initialized();
+    let mut instruction = env.approve(&Pubkey::new_unique(), 500, 0, 0);
+    instruction.accounts[4].pubkey = delegate_record_address(&env.vault, &Pubkey::new_unique());
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidDelegateRecord));
+}
+
+// ApproveInstructionData
+
+#[test]
+fn rejects_zero_allowance() {
+    let mut env = Env::initialized();
+
+    env.expect_err(
+        &env.approve(&Pubkey::new_unique(), 0, 0, 0),
+        vault_error(VaultError::ZeroAmount),
+    );
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.approve(&Pubkey::new_unique(), 500, 0, 0);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
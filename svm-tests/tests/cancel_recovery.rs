@@ -0,0 +1,112 @@
+mod common;
+
+use blueshift_vault::{RecoveryState, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::pubkey::Pubkey;
+
+const CHALLENGE_PERIOD: i64 = 86_400;
+
+// A vault with 2-of-3 guardians and an open proposal. Returns the guardians and the payer.
+fn proposed_env() -> (Env, [Pubkey; 3], Pubkey) {
+    let mut env = Env::funded(1_000_000_000);
+    let guardians = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    env.process(&env.set_guardians(2, CHALLENGE_PERIOD, &guardians));
+    let payer = Pubkey::new_unique();
+    env.fund(&payer, OWNER_LAMPORTS);
+    env.process(&env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[..2]));
+    (env, guardians, payer)
+}
+
+#[test]
+fn owner_cancels_during_the_challenge_period() {
+    let (mut env, _, payer) = proposed_env();
+    let recovery = recovery_address(&env.vault);
+    let payer_before = env.lamports(&payer);
+
+    env.warp(CHALLENGE_PERIOD - 1);
+    env.process(&env.cancel_recovery(&payer));
+
+    assert_eq!(env.lamports(&recovery), 0);
+    assert_eq!(
+        env.lamports(&payer),
+        payer_before + env.rent_exempt(RecoveryState::LEN)
+    );
+    assert_eq!(env.open_accounts(), 0);
+
+    // The owner keeps the vault, and the cancelled proposal can't be executed.
+    env.warp(1);
+    env.expect_err(
+        &env.execute_recovery(&payer),
+        vault_error(VaultError::InvalidRecovery),
+    );
+    let account = env.account(&env.state);
+    assert_eq!(VaultState::load(&account.data).unwrap().owner(), &env.key());
+}
+
+#[test]
+fn owner_can_cancel_after_the_challenge_period() {
+    // Until somebody executes it, the proposal can still be rejected.
+    let (mut env, _, payer) = proposed_env();
+
+    env.warp(CHALLENGE_PERIOD);
+    env.process(&env.cancel_recovery(&payer));
+
+    assert_eq!(env.lamports(&recovery_address(&env.vault)), 0);
+}
+
+#[test]
+fn guardians_can_propose_again_after_a_cancel() {
+    let (mut env, guardians, payer) = proposed_env();
+    env.process(&env.cancel_recovery(&payer));
+
+    env.process(&env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[1..]));
+
+    assert_eq!(env.open_accounts(), 1);
+}
+
+// CancelRecoveryAccounts
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    // The guardians can propose a recovery, but only the owner can reject one.
+    let (mut env, guardians, payer) = proposed_env();
+    let mut instruction = env.cancel_recovery(&payer);
+    instruction.accounts[0].pubkey = guardians[0];
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let (mut env, _, payer) = proposed_env();
+    let mut instruction = env.cancel_recovery(&payer);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_another_payer() {
+    // The rent goes back to whoever paid for the proposal, not to the owner.
+    let (mut env, _, _) = proposed_env();
+    let owner = env.owner;
+
+    env.expect_err(
+        &env.cancel_recovery(&owner),
+        vault_error(VaultError::InvalidRecovery),
+    );
+}
+
+#[test]
+fn rejects_without_a_proposal() {
+    let mut env = Env::initialized();
+
+    env.expect_err(
+        &env.cancel_recovery(&Pubkey::new_unique()),
+        vault_error(VaultError::InvalidRecovery),
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use blueshift_vault::{PendingWithdrawal, VaultError};
+use common::*;
+use solana_sdk::pubkey::Pubkey;
+
+const BALANCE: u64 = 1_000_000_000;
+const DELAY: i64 = 3_600;
+
+// A funded vault with a withdrawal to the returned destination queued as request 1.
+fn queued_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.set_withdraw_delay(DELAY));
+    let destination = Pubkey::new_unique();
+    env.process(&env.request_withdraw(&destination, 1, 400_000_000));
+    (env, destination)
+}
+
+#[test]
+fn cancel_closes_the_pending_withdrawal() {
+    let (mut env, destination) = queued_env();
+    let pending = pending_withdrawal_address(&env.vault, 1);
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&env.cancel_withdraw(1));
+
+    assert_eq!(env.lamports(&pending), 0);
+    assert_eq!(
+        env.lamports(&env.owner),
+        owner_before + env.rent_exempt(PendingWithdrawal::LEN)
+    );
+    assert_eq!(env.lamports(&env.vault), BALANCE);
+    assert_eq!(env.open_accounts(), 0);
+
+    // Once cancelled, it can't be executed.
+    env.warp(DELAY);
+    env.expect_err(
+        &env.execute_withdraw(1, &destination),
+        vault_error(VaultError::InvalidPendingWithdrawal),
+    );
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let (mut env, _) = queued_env();
+    let intruder = Pubkey::new_unique();
+    let mut instruction = env.cancel_withdraw(1);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let (mut env, _) = queued_env();
+    let mut instruction = env.cancel_withdraw(1);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_pending_withdrawal_of_another_vault() {
+    let (mut env, destination) = queued_env();
+    env.initialize(INDEX + 1, 0);
+    let other_vault = vault_address(&env.owner, INDEX + 1);
+    env.process(&to_sdk(blueshift_vault::client::request_withdraw(
+        &env.key(),
+        &env.key(),
+        INDEX + 1,
+        &destination.to_bytes(),
+        1,
+        400_000_000,
+    )));
+    let mut instruction = env.cancel_withdraw(1);
+    instruction.accounts[3].pubkey = pending_withdrawal_address(&other_vault, 1);
+
+    env.expect_err(
+        &instruction,
+        vault_error(VaultError::InvalidPendingWithdrawal),
+    );
+}
+
+#[test]
+fn rejects_unknown_request() {
+    let (mut env, _) = queued_env();
+
+    env.expect_err(
+        &env.cancel_withdraw(2),
+        vault_error(VaultError::InvalidPendingWithdrawal),
+    );
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use blueshift_vault::VaultError;
+use common::*;
+use solana_sdk::pubkey::Pubkey;
+
+const BALANCE: u64 = 1_000_000_000;
+const INACTIVITY_PERIOD: i64 = 60;
+
+// A funded vault whose heir, returned, can claim after `INACTIVITY_PERIOD` seconds of inactivity.
+fn inheritance_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let heir = Pubkey::new_unique();
+    env.process(&env.set_inheritance(&heir, INACTIVITY_PERIOD));
+    (env, heir)
+}
+
+#[test]
+fn heir_claims_everything() {
+    let (mut env, heir) = inheritance_env();
+    env.warp(INACTIVITY_PERIOD);
+
+    env.process(&env.claim(&heir, None));
+
+    assert_eq!(env.lamports(&heir), BALANCE);
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn heir_claims_part_of_the_balance() {
+    let (mut env, heir) = inheritance_env();
+    env.warp(INACTIVITY_PERIOD);
+
+    env.process(&env.claim(&heir, Some(BALANCE / 4)));
+
+    assert_eq!(env.lamports(&heir), BALANCE / 4);
+    assert_eq!(env.lamports(&env.vault), BALANCE * 3 / 4);
+}
+
+#[test]
+fn rejects_owner_still_active() {
+    let (mut env, heir) = inheritance_env();
+    env.warp(INACTIVITY_PERIOD - 1);
+
+    env.expect_err(
+        &env.claim(&heir, None),
+        vault_error(VaultError::OwnerStillActive),
+    );
+}
+
+#[test]
+fn claim_waits_out_the_withdrawal_delay() {
+    // A key that names itself heir with a short period still has to wait as long as a withdrawal.
+    let mut env = Env::funded(BALANCE);
+    let heir = Pubkey::new_unique();
+    env.process(&env.set_withdraw_delay(3_600));
+    env.process(&env.set_inheritance(&heir, INACTIVITY_PERIOD));
+
+    env.warp(INACTIVITY_PERIOD);
+    env.expect_err(
+        &env.claim(&heir, None),
+        vault_error(VaultError::OwnerStillActive),
+    );
+
+    env.warp(3_600 - INACTIVITY_PERIOD);
+    env.process(&env.claim(&heir, None));
+    assert_eq!(env.lamports(&heir), BALANCE);
+}
+
+#[test]
+fn claim_respects_the_time_lock() {
+    let mut env = Env::new();
+    env.initialize(INDEX, NOW + 3_600);
+    env.process(&env.deposit(BALANCE));
+    let heir = Pubkey::new_unique();
+    env.process(&env.set_inheritance(&heir, INACTIVITY_PERIOD));
+
+    env.warp(INACTIVITY_PERIOD);
+
+    env.expect_err(
+        &env.claim(&heir, None),
+        vault_error(VaultError::VaultLocked),
+    );
+}
+
+// Claim accounts
+
+#[test]
+fn rejects_stranger() {
+    let (mut env, _) = inheritance_env();
+    env.warp(INACTIVITY_PERIOD);
+
+    env.expect_err(
+        &env.claim(&Pubkey::new_unique(), None),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn rejects_vault_without_inheritance() {
+    let mut env = Env::funded(BALANCE);
+    env.warp(365 * 86_400);
+
+    env.expect_err(
+        &env.claim(&Pubkey::new_unique(), None),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn rejects_heir_not_signer() {
+    let (mut env, heir) = inheritance_env();
+    env.warp(INACTIVITY_PERIOD);
+    let mut instruction = env.claim(&heir, None);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_wrong_owner() {
+    // The owner account only identifies the vault, but it still has to be the right one.
+    let (mut env, heir) = inheritance_env();
+    env.warp(INACTIVITY_PERIOD);
+    let mut instruction = env.claim(&heir, None);
+    instruction.accounts[1].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
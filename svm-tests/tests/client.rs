@@ -0,0 +1,56 @@
+// Compares the client's host PDA derivation with the SDK's `Pubkey::find_program_address` over
+// many random seeds, on top of the fixed known-answer tests in the program crate.
+
+use blueshift_vault::{client, Allowlist, DelegateRecord, PendingWithdrawal, RecoveryState};
+use solana_sdk::pubkey::Pubkey;
+
+fn program_id() -> Pubkey {
+    Pubkey::new_from_array(blueshift_vault::ID)
+}
+
+fn expected(seeds: &[&[u8]]) -> Option<([u8; 32], u8)> {
+    let (address, bump) = Pubkey::find_program_address(seeds, &program_id());
+    Some((address.to_bytes(), bump))
+}
+
+#[test]
+fn pdas_match_the_sdk() {
+    let mut low_bumps = 0;
+
+    for index in 0..256u64 {
+        let creator = Pubkey::new_unique().to_bytes();
+        let (vault, bump) = client::vault_address(&creator, index).unwrap();
+        assert_eq!(
+            Some((vault, bump)),
+            expected(&[b"vault", &creator, &index.to_le_bytes()])
+        );
+        if bump < u8::MAX {
+            low_bumps += 1;
+        }
+
+        let delegate = Pubkey::new_unique().to_bytes();
+        assert_eq!(
+            client::state_address(&vault),
+            expected(&[blueshift_vault::VaultState::SEED, &vault])
+        );
+        assert_eq!(
+            client::delegate_record_address(&vault, &delegate),
+            expected(&[DelegateRecord::SEED, &vault, &delegate])
+        );
+        assert_eq!(
+            client::pending_withdrawal_address(&vault, index),
+            expected(&[PendingWithdrawal::SEED, &vault, &index.to_le_bytes()])
+        );
+        assert_eq!(
+            client::allowlist_address(&vault),
+            expected(&[Allowlist::SEED, &vault])
+        );
+        assert_eq!(
+            client::recovery_address(&vault),
+            expected(&[RecoveryState::SEED, &vault])
+        );
+    }
+
+    // About half of all seeds need a bump below 255, so the search loop is exercised too.
+    assert!(low_bumps > 0);
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use blueshift_vault::{client, token::TOKEN_PROGRAM_ID, VaultError};
+use common::*;
+use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+
+// Close of vault `INDEX`, closing `closing` and the vault's token accounts for the `tokens` mints.
+fn close(env: &Env, closing: &[Pubkey], tokens: &[Pubkey]) -> Instruction {
+    let closing: Vec<_> = closing.iter().map(|account| account.to_bytes()).collect();
+    let tokens: Vec<_> = tokens
+        .iter()
+        .map(|mint| (mint.to_bytes(), TOKEN_PROGRAM_ID))
+        .collect();
+    to_sdk(client::close(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &closing,
+        &tokens,
+    ))
+}
+
+#[test]
+fn close_drains_the_vault_and_closes_the_state() {
+    let mut env = Env::funded(BALANCE);
+    let state = env.state;
+    let owner_before = env.lamports(&env.owner);
+    let state_rent = env.lamports(&state);
+
+    env.process(&close(&env, &[], &[]));
+
+    assert_eq!(
+        env.lamports(&env.owner),
+        owner_before + BALANCE + state_rent
+    );
+    assert_eq!(env.lamports(&env.vault), 0);
+    assert_eq!(env.lamports(&state), 0);
+}
+
+#[test]
+fn close_closes_empty_token_accounts() {
+    let mut env = Env::funded(BALANCE);
+    let (mint, _) = env.add_tokens(0, 0);
+    let vault_token_account = vault_token_account(&env.vault, &mint);
+    let owner_before = env.lamports(&env.owner);
+    let rent = env.lamports(&env.state) + env.lamports(&vault_token_account);
+
+    env.process(&close(&env, &[], &[mint]));
+
+    assert_eq!(env.lamports(&vault_token_account), 0);
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE + rent);
+}
+
+#[test]
+fn close_closes_the_listed_accounts() {
+    let mut env = Env::funded(BALANCE);
+    let delegate = Pubkey::new_unique();
+    env.process(&env.approve(&delegate, 500, 0, 0));
+    let record = delegate_record_address(&env.vault, &delegate);
+    let owner_before = env.lamports(&env.owner);
+    let rent = env.lamports(&env.state) + env.lamports(&record);
+
+    env.process(&close(&env, &[record], &[]));
+
+    assert_eq!(env.lamports(&record), 0);
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE + rent);
+}
+
+#[test]
+fn rejects_unlisted_open_accounts() {
+    // A delegate record left behind would work again if the vault were initialized again.
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.approve(&Pubkey::new_unique(), 500, 0, 0));
+
+    env.expect_err(
+        &close(&env, &[], &[]),
+        vault_error(VaultError::VaultHasOpenAccounts),
+    );
+}
+
+#[test]
+fn unlisted_token_accounts_are_not_checked() {
+    // The program keeps no record of the vault's token accounts, so it can't notice a missing one.
+    // Its tokens stay behind, which is why the caller must list every token account.
+    let mut env = Env::funded(BALANCE);
+    let (mint, _) = env.add_tokens(0, 1_000);
+
+    env.process(&close(&env, &[], &[]));
+
+    assert_eq!(
+        env.token_amount(&vault_token_account(&env.vault, &mint)),
+        1_000
+    );
+}
+
+#[test]
+fn rejects_token_account_holding_tokens() {
+    // Tokens left behind would go to whoever initializes the vault again.
+    let mut env = Env::funded(BALANCE);
+    let (mint, _) = env.add_tokens(0, 1_000);
+
+    env.expect_err(
+        &close(&env, &[], &[mint]),
+        vault_error(VaultError::VaultHasTokens),
+    );
+}
+
+#[test]
+fn rejects_token_account_not_held_by_the_vault() {
+    let mut env = Env::funded(BALANCE);
+    let (mint, _) = env.add_tokens(0, 0);
+    let vault_token_account = vault_token_account(&env.vault, &mint);
+    env.set_token_account(&vault_token_account, &mint, &Pubkey::new_unique(), 0);
+
+    env.expect_err(
+        &close(&env, &[], &[mint]),
+        vault_error(VaultError::InvalidTokenAccount),
+    );
+}
+
+#[test]
+fn rejects_missing_token_accounts() {
+    let mut env = Env::funded(BALANCE);
+    let (mint, _) = env.add_tokens(0, 0);
+    let mut instruction = close(&env, &[], &[mint]);
+    instruction.accounts.pop();
+
+    env.expect_err(&instruction, ProgramError::NotEnoughAccountKeys);
+}
@@ -0,0 +1,607 @@
+// Shared setup for the SVM tests: a Mollusk instance running the compiled program, plus an
+// in-memory ledger of accounts carried from one instruction to the next.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use blueshift_vault::{
+    client, token::TOKEN_PROGRAM_ID, InitializeInstructionData, VaultError, VaultState,
+    ZeroCopyAccount,
+};
+use mollusk_svm::{
+    program::keyed_account_for_system_program,
+    result::{Check, InstructionResult, ProgramResult},
+    Mollusk,
+};
+use mollusk_svm_programs_token::token;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// Vault index used by every test, deliberately not 0 so a missing index would show.
+pub const INDEX: u64 = 7;
+
+// Starting balance of the owner, 10 SOL.
+pub const OWNER_LAMPORTS: u64 = 10_000_000_000;
+
+// Clock of the test bank. Initialize, Deposit and Withdraw all read it.
+pub const NOW: i64 = 1_700_000_000;
+
+pub fn program_id() -> Pubkey {
+    Pubkey::new_from_array(blueshift_vault::ID)
+}
+
+pub fn system_program() -> Pubkey {
+    keyed_account_for_system_program().0
+}
+
+// `ProgramError::Custom` carrying the code of a vault error.
+pub fn vault_error(error: VaultError) -> ProgramError {
+    ProgramError::Custom(error as u32)
+}
+
+pub fn vault_address(creator: &Pubkey, index: u64) -> Pubkey {
+    Pubkey::new_from_array(client::vault_address(&creator.to_bytes(), index).unwrap().0)
+}
+
+// A valid PDA of vault `index` derived with a bump below the canonical one.
+pub fn non_canonical_vault_address(creator: &Pubkey, index: u64) -> Pubkey {
+    let (_, canonical_bump) = client::vault_address(&creator.to_bytes(), index).unwrap();
+    (0..canonical_bump)
+        .rev()
+        .find_map(|bump| {
+            Pubkey::create_program_address(
+                &[b"vault", creator.as_ref(), &index.to_le_bytes(), &[bump]],
+                &program_id(),
+            )
+            .ok()
+        })
+        .unwrap()
+}
+
+pub fn legacy_vault_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(client::legacy_vault_address(&owner.to_bytes()).unwrap().0)
+}
+
+pub fn state_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(client::state_address(&vault.to_bytes()).unwrap().0)
+}
+
+pub fn allowlist_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(client::allowlist_address(&vault.to_bytes()).unwrap().0)
+}
+
+pub fn delegate_record_address(vault: &Pubkey, delegate: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(
+        client::delegate_record_address(&vault.to_bytes(), &delegate.to_bytes())
+            .unwrap()
+            .0,
+    )
+}
+
+pub fn pending_withdrawal_address(vault: &Pubkey, request_id: u64) -> Pubkey {
+    Pubkey::new_from_array(
+        client::pending_withdrawal_address(&vault.to_bytes(), request_id)
+            .unwrap()
+            .0,
+    )
+}
+
+pub fn vault_token_account(vault: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(
+        client::associated_token_address(&vault.to_bytes(), &mint.to_bytes(), &TOKEN_PROGRAM_ID)
+            .unwrap()
+            .0,
+    )
+}
+
+pub fn recovery_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::new_from_array(client::recovery_address(&vault.to_bytes()).unwrap().0)
+}
+
+// Converts an instruction built by the crate's client into the SDK type Mollusk expects. Every
+// instruction in the tests is built this way, so they follow the program's data layouts.
+pub fn to_sdk(instruction: Option<client::Instruction>) -> Instruction {
+    let instruction = instruction.expect("no viable bump for a PDA");
+    Instruction {
+        program_id: Pubkey::new_from_array(instruction.program_id),
+        accounts: instruction
+            .accounts
+            .into_iter()
+            .map(|meta| AccountMeta {
+                pubkey: Pubkey::new_from_array(meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: instruction.data,
+    }
+}
+
+pub struct Env {
+    pub mollusk: Mollusk,
+    pub accounts: HashMap<Pubkey, Account>,
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub state: Pubkey,
+}
+
+impl Env {
+    // A funded owner whose vault `INDEX` doesn't exist yet.
+    pub fn new() -> Self {
+        // Mollusk loads `blueshift_vault.so` from `SBF_OUT_DIR`; default to the output of
+        // `cargo build-sbf` in the program's directory.
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            std::env::set_var(
+                "SBF_OUT_DIR",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/../target/deploy"),
+            );
+        }
+
+        let mut mollusk = Mollusk::new(&program_id(), "blueshift_vault");
+        mollusk.sysvars.clock.unix_timestamp = NOW;
+
+        let owner = Pubkey::new_unique();
+        let vault = vault_address(&owner, INDEX);
+        let state = state_address(&vault);
+
+        let mut env = Self {
+            mollusk,
+            accounts: HashMap::new(),
+            owner,
+            vault,
+            state,
+        };
+        env.fund(&owner, OWNER_LAMPORTS);
+        env
+    }
+
+    // An initialized vault `INDEX` without any lamports.
+    pub fn initialized() -> Self {
+        let mut env = Self::new();
+        env.initialize(INDEX, 0);
+        env
+    }
+
+    // An initialized vault `INDEX` holding `amount` lamports, deposited by the owner.
+    pub fn funded(amount: u64) -> Self {
+        let mut env = Self::initialized();
+        env.process(&env.deposit(amount));
+        env
+    }
+
+    // Minimum balance of a rent-exempt account of `len` bytes.
+    pub fn rent_exempt(&self, len: usize) -> u64 {
+        self.mollusk.sysvars.rent.minimum_balance(len)
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |account| account.lamports)
+    }
+
+    // Replaces `key` with a system account holding `lamports`.
+    pub fn fund(&mut self, key: &Pubkey, lamports: u64) {
+        self.accounts
+            .insert(*key, Account::new(lamports, 0, &system_program()));
+    }
+
+    pub fn set_account(&mut self, key: &Pubkey, account: Account) {
+        self.accounts.insert(*key, account);
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Account {
+        self.accounts.get(key).cloned().unwrap_or_default()
+    }
+
+    // Loads the Token program and creates a mint, a token account of the owner and the vault's
+    // associated token account. Returns the mint and the owner's token account.
+    pub fn add_tokens(&mut self, owner_amount: u64, vault_amount: u64) -> (Pubkey, Pubkey) {
+        token::add_program(&mut self.mollusk);
+        let (token_program, token_program_account) = token::keyed_account();
+        self.set_account(&token_program, token_program_account);
+
+        let mint = Pubkey::new_unique();
+        // [mint_authority: 36][supply: 8][decimals: 1][is_initialized: 1][freeze_authority: 36]
+        let mut data = vec![0; 82];
+        data[36..44].copy_from_slice(&(owner_amount + vault_amount).to_le_bytes());
+        data[44] = 9;
+        data[45] = 1;
+        self.set_account(&mint, self.owned_by_token_program(data));
+
+        let owner_token_account = Pubkey::new_unique();
+        let owner = self.owner;
+        self.set_token_account(&owner_token_account, &mint, &owner, owner_amount);
+
+        let vault = self.vault;
+        self.set_token_account(
+            &vault_token_account(&vault, &mint),
+            &mint,
+            &vault,
+            vault_amount,
+        );
+
+        (mint, owner_token_account)
+    }
+
+    // Replaces `key` with an initialized token account of `mint` owned by `owner`.
+    pub fn set_token_account(&mut self, key: &Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        // [mint: 32][owner: 32][amount: 8][delegate: 36][state: 1][is_native: 12][delegated_amount: 8][close_authority: 36]
+        let mut data = vec![0; 165];
+        data[0..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        data[108] = 1;
+        self.set_account(key, self.owned_by_token_program(data));
+    }
+
+    pub fn token_amount(&self, key: &Pubkey) -> u64 {
+        let data = self.account(key).data;
+        u64::from_le_bytes(data[64..72].try_into().unwrap())
+    }
+
+    fn owned_by_token_program(&self, data: Vec<u8>) -> Account {
+        Account {
+            lamports: self.rent_exempt(data.len()),
+            data,
+            owner: Pubkey::new_from_array(TOKEN_PROGRAM_ID),
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    pub fn warp(&mut self, seconds: i64) {
+        self.mollusk.sysvars.clock.unix_timestamp += seconds;
+    }
+
+    // The accounts referenced by `instruction`, taken from the ledger. Unknown keys are empty
+    // system accounts.
+    fn accounts_for(&self, instruction: &Instruction) -> Vec<(Pubkey, Account)> {
+        let mut accounts: Vec<(Pubkey, Account)> = Vec::new();
+        for meta in &instruction.accounts {
+            if accounts.iter().any(|(key, _)| key.eq(&meta.pubkey)) {
+                continue;
+            }
+            if meta.pubkey.eq(&system_program()) {
+                accounts.push(keyed_account_for_system_program());
+            } else {
+                accounts.push((meta.pubkey, self.account(&meta.pubkey)));
+            }
+        }
+        accounts
+    }
+
+    // Runs `instruction`, which must succeed, and writes the resulting accounts back to the ledger.
+    // Returns the compute units it consumed.
+    pub fn process(&mut self, instruction: &Instruction) -> u64 {
+        let accounts = self.accounts_for(instruction);
+        let result = self.mollusk.process_and_validate_instruction(
+            instruction,
+            &accounts,
+            &[Check::success()],
+        );
+        for (key, account) in result.resulting_accounts {
+            if key.ne(&system_program()) {
+                self.accounts.insert(key, account);
+            }
+        }
+        result.compute_units_consumed
+    }
+
+    // Runs `instruction`, which may fail, writing the resulting accounts back to the ledger only if
+    // it succeeded. Returns the accounts it was given along with the result.
+    pub fn try_process(
+        &mut self,
+        instruction: &Instruction,
+    ) -> (Vec<(Pubkey, Account)>, InstructionResult) {
+        let accounts = self.accounts_for(instruction);
+        let result = self.mollusk.process_instruction(instruction, &accounts);
+        if matches!(result.program_result, ProgramResult::Success) {
+            for (key, account) in &result.resulting_accounts {
+                if key.ne(&system_program()) {
+                    self.accounts.insert(*key, account.clone());
+                }
+            }
+        }
+        (accounts, result)
+    }
+
+    // Runs `instruction`, which must fail with `error` without moving any lamports.
+    pub fn expect_err(&mut self, instruction: &Instruction, error: ProgramError) {
+        let accounts = self.accounts_for(instruction);
+        let mut checks = vec![Check::err(error)];
+        for (key, account) in &accounts {
+            checks.push(Check::account(key).lamports(account.lamports).build());
+        }
+        self.mollusk
+            .process_and_validate_instruction(instruction, &accounts, &checks);
+    }
+
+    // Creates the state of vault `index`, with an optional time lock and no vesting schedule.
+    pub fn initialize(&mut self, index: u64, unlock_ts: i64) {
+        self.process(&self.initialize_instruction(index, unlock_ts));
+
+        let state = state_address(&vault_address(&self.owner, index));
+        assert_eq!(self.lamports(&state), self.rent_exempt(VaultState::LEN));
+    }
+
+    pub fn initialize_instruction(&self, index: u64, unlock_ts: i64) -> Instruction {
+        to_sdk(client::initialize(
+            &self.owner.to_bytes(),
+            &InitializeInstructionData {
+                index,
+                unlock_ts,
+                start_ts: 0,
+                cliff_ts: 0,
+                end_ts: 0,
+                beneficiary: [0; 32],
+                label: [0; 32],
+            },
+        ))
+    }
+
+    // Initialize for vault `INDEX` with a vesting schedule released to `beneficiary`.
+    pub fn vesting_instruction(
+        &self,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        beneficiary: &Pubkey,
+    ) -> Instruction {
+        to_sdk(client::initialize(
+            &self.owner.to_bytes(),
+            &InitializeInstructionData {
+                index: INDEX,
+                unlock_ts: 0,
+                start_ts,
+                cliff_ts,
+                end_ts,
+                beneficiary: beneficiary.to_bytes(),
+                label: [0; 32],
+            },
+        ))
+    }
+
+    // The owner's key as the client takes it. The owner is also the creator of vault `INDEX`.
+    pub fn key(&self) -> [u8; 32] {
+        self.owner.to_bytes()
+    }
+
+    pub fn deposit(&self, amount: u64) -> Instruction {
+        to_sdk(client::deposit(&self.key(), &self.key(), INDEX, amount))
+    }
+
+    pub fn withdraw(&self, amount: Option<u64>) -> Instruction {
+        to_sdk(client::withdraw(&self.key(), &self.key(), INDEX, amount))
+    }
+
+    // Approve giving `delegate` an allowance on vault `INDEX`.
+    pub fn approve(
+        &self,
+        delegate: &Pubkey,
+        allowance: u64,
+        expiry_slot: u64,
+        per_tx_cap: u64,
+    ) -> Instruction {
+        to_sdk(client::approve(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &delegate.to_bytes(),
+            allowance,
+            expiry_slot,
+            per_tx_cap,
+        ))
+    }
+
+    pub fn set_withdraw_delay(&self, delay: i64) -> Instruction {
+        to_sdk(client::set_withdraw_delay(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            delay,
+        ))
+    }
+
+    // RequestWithdraw queueing `amount` lamports of vault `INDEX` to `destination`.
+    pub fn request_withdraw(
+        &self,
+        destination: &Pubkey,
+        request_id: u64,
+        amount: u64,
+    ) -> Instruction {
+        to_sdk(client::request_withdraw(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &destination.to_bytes(),
+            request_id,
+            amount,
+        ))
+    }
+
+    pub fn execute_withdraw(&self, request_id: u64, destination: &Pubkey) -> Instruction {
+        to_sdk(client::execute_withdraw(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            request_id,
+            &destination.to_bytes(),
+        ))
+    }
+
+    pub fn cancel_withdraw(&self, request_id: u64) -> Instruction {
+        to_sdk(client::cancel_withdraw(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            request_id,
+        ))
+    }
+
+    // Number of delegate records, pending withdrawals and other accounts holding up Close.
+    pub fn open_accounts(&self) -> u32 {
+        let account = self.account(&self.state);
+        VaultState::load(&account.data).unwrap().open_accounts()
+    }
+
+    pub fn add_destination(&self, destination: &Pubkey) -> Instruction {
+        to_sdk(client::add_destination(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &destination.to_bytes(),
+        ))
+    }
+
+    pub fn remove_destination(&self, destination: &Pubkey) -> Instruction {
+        to_sdk(client::remove_destination(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &destination.to_bytes(),
+        ))
+    }
+
+    // WithdrawTo of `amount` lamports to `destination`, passing the vault's allowlist.
+    pub fn withdraw_to_allowed(&self, destination: &Pubkey, amount: Option<u64>) -> Instruction {
+        let mut instruction = to_sdk(client::withdraw_to(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &destination.to_bytes(),
+            amount,
+        ));
+        instruction.accounts.push(AccountMeta::new_readonly(
+            allowlist_address(&self.vault),
+            false,
+        ));
+        instruction
+    }
+
+    pub fn transfer_ownership(&self, new_owner: &Pubkey) -> Instruction {
+        to_sdk(client::transfer_ownership(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &new_owner.to_bytes(),
+        ))
+    }
+
+    pub fn accept_ownership(&self, new_owner: &Pubkey) -> Instruction {
+        to_sdk(client::accept_ownership(
+            &new_owner.to_bytes(),
+            &self.key(),
+            INDEX,
+        ))
+    }
+
+    pub fn set_guardians(
+        &self,
+        threshold: u8,
+        challenge_period: i64,
+        guardians: &[Pubkey],
+    ) -> Instruction {
+        let guardians: Vec<[u8; 32]> = guardians
+            .iter()
+            .map(|guardian| guardian.to_bytes())
+            .collect();
+        to_sdk(client::set_guardians(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            threshold,
+            challenge_period,
+            &guardians,
+        ))
+    }
+
+    // ProposeRecovery handing vault `INDEX` to `new_owner`, paid for by `payer` and signed by `guardians`.
+    pub fn propose_recovery(
+        &self,
+        payer: &Pubkey,
+        new_owner: &Pubkey,
+        guardians: &[Pubkey],
+    ) -> Instruction {
+        let guardians: Vec<[u8; 32]> = guardians
+            .iter()
+            .map(|guardian| guardian.to_bytes())
+            .collect();
+        to_sdk(client::propose_recovery(
+            &payer.to_bytes(),
+            &self.key(),
+            INDEX,
+            &new_owner.to_bytes(),
+            &guardians,
+        ))
+    }
+
+    pub fn cancel_recovery(&self, payer: &Pubkey) -> Instruction {
+        to_sdk(client::cancel_recovery(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &payer.to_bytes(),
+        ))
+    }
+
+    pub fn execute_recovery(&self, payer: &Pubkey) -> Instruction {
+        to_sdk(client::execute_recovery(
+            &self.key(),
+            INDEX,
+            &payer.to_bytes(),
+        ))
+    }
+
+    pub fn heartbeat(&self) -> Instruction {
+        to_sdk(client::heartbeat(&self.key(), &self.key(), INDEX))
+    }
+
+    pub fn set_inheritance(&self, heir: &Pubkey, inactivity_period: i64) -> Instruction {
+        to_sdk(client::set_inheritance(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &heir.to_bytes(),
+            inactivity_period,
+        ))
+    }
+
+    // Claim of `amount` lamports (everything with `None`) from vault `INDEX`, signed by `heir`.
+    pub fn claim(&self, heir: &Pubkey, amount: Option<u64>) -> Instruction {
+        to_sdk(client::claim(
+            &heir.to_bytes(),
+            &self.key(),
+            &self.key(),
+            INDEX,
+            amount,
+        ))
+    }
+
+    pub fn set_freeze_authority(&self, freeze_authority: &Pubkey) -> Instruction {
+        to_sdk(client::set_freeze_authority(
+            &self.key(),
+            &self.key(),
+            INDEX,
+            &freeze_authority.to_bytes(),
+        ))
+    }
+
+    pub fn freeze(&self, freeze_authority: &Pubkey) -> Instruction {
+        to_sdk(client::freeze(
+            &freeze_authority.to_bytes(),
+            &self.key(),
+            INDEX,
+        ))
+    }
+
+    pub fn thaw(&self, freeze_authority: &Pubkey) -> Instruction {
+        to_sdk(client::thaw(
+            &freeze_authority.to_bytes(),
+            &self.key(),
+            INDEX,
+        ))
+    }
+}
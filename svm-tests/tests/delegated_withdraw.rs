@@ -0,0 +1,120 @@
+mod common;
+
+use blueshift_vault::{client, DelegateRecord, VaultError, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+const ALLOWANCE: u64 = 500_000_000;
+
+fn delegated_withdraw(env: &Env, delegate: &Pubkey, amount: u64) -> Instruction {
+    to_sdk(client::delegated_withdraw(
+        &delegate.to_bytes(),
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &delegate.to_bytes(),
+        Some(amount),
+    ))
+}
+
+// A funded vault and a delegate approved with `ALLOWANCE`, the given expiry slot and cap.
+fn delegate_env(expiry_slot: u64, per_tx_cap: u64) -> (Env, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let delegate = Pubkey::new_unique();
+    env.process(&env.approve(&delegate, ALLOWANCE, expiry_slot, per_tx_cap));
+    (env, delegate)
+}
+
+fn allowance(env: &Env, delegate: &Pubkey) -> u64 {
+    let account = env.account(&delegate_record_address(&env.vault, delegate));
+    DelegateRecord::load(&account.data).unwrap().allowance()
+}
+
+#[test]
+fn spending_decreases_the_allowance() {
+    let (mut env, delegate) = delegate_env(0, 0);
+
+    env.process(&delegated_withdraw(&env, &delegate, 200_000_000));
+
+    assert_eq!(env.lamports(&delegate), 200_000_000);
+    assert_eq!(env.lamports(&env.vault), BALANCE - 200_000_000);
+    assert_eq!(allowance(&env, &delegate), ALLOWANCE - 200_000_000);
+
+    env.process(&delegated_withdraw(&env, &delegate, 300_000_000));
+
+    assert_eq!(env.lamports(&delegate), ALLOWANCE);
+    assert_eq!(allowance(&env, &delegate), 0);
+}
+
+#[test]
+fn rejects_amount_above_the_allowance() {
+    let (mut env, delegate) = delegate_env(0, 0);
+    env.process(&delegated_withdraw(&env, &delegate, 200_000_000));
+
+    env.expect_err(
+        &delegated_withdraw(&env, &delegate, ALLOWANCE - 200_000_000 + 1),
+        vault_error(VaultError::AllowanceExceeded),
+    );
+}
+
+#[test]
+fn rejects_amount_above_the_per_transaction_cap() {
+    let (mut env, delegate) = delegate_env(0, 100_000_000);
+
+    env.expect_err(
+        &delegated_withdraw(&env, &delegate, 100_000_001),
+        vault_error(VaultError::PerTransactionCapExceeded),
+    );
+
+    // The cap applies per withdrawal, not to the allowance as a whole.
+    env.process(&delegated_withdraw(&env, &delegate, 100_000_000));
+    env.process(&delegated_withdraw(&env, &delegate, 100_000_000));
+
+    assert_eq!(allowance(&env, &delegate), ALLOWANCE - 200_000_000);
+}
+
+#[test]
+fn allowance_expires_after_its_slot() {
+    let (mut env, delegate) = delegate_env(100, 0);
+
+    // The expiry slot itself is still valid.
+    env.mollusk.sysvars.clock.slot = 100;
+    env.process(&delegated_withdraw(&env, &delegate, 100_000_000));
+
+    env.mollusk.sysvars.clock.slot = 101;
+    env.expect_err(
+        &delegated_withdraw(&env, &delegate, 100_000_000),
+        vault_error(VaultError::DelegationExpired),
+    );
+}
+
+#[test]
+fn rejects_delegate_not_signer() {
+    let (mut env, delegate) = delegate_env(0, 0);
+    let mut instruction = delegated_withdraw(&env, &delegate, 1);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_record_of_another_delegate() {
+    // A second delegate can't spend the first one's allowance.
+    let (mut env, delegate) = delegate_env(0, 0);
+    let other = Pubkey::new_unique();
+    let mut instruction = delegated_withdraw(&env, &other, 1);
+    instruction.accounts[4].pubkey = delegate_record_address(&env.vault, &delegate);
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidDelegateRecord));
+}
+
+#[test]
+fn rejects_delegate_without_record() {
+    let (mut env, _) = delegate_env(0, 0);
+
+    env.expect_err(
+        &delegated_withdraw(&env, &Pubkey::new_unique(), 1),
+        vault_error(VaultError::InvalidDelegateRecord),
+    );
+}
@@ -0,0 +1,246 @@
+mod common;
+
+use blueshift_vault::{VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{
+    account::Account, instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey,
+};
+
+#[test]
+fn first_deposit_funds_the_vault() {
+    let mut env = Env::initialized();
+    let amount = env.rent_exempt(0);
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&env.deposit(amount));
+
+    assert_eq!(env.lamports(&env.owner), owner_before - amount);
+    assert_eq!(env.lamports(&env.vault), amount);
+}
+
+#[test]
+fn deposit_tops_up_the_vault() {
+    let mut env = Env::funded(1_000_000_000);
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&env.deposit(1));
+
+    assert_eq!(env.lamports(&env.owner), owner_before - 1);
+    assert_eq!(env.lamports(&env.vault), 1_000_000_001);
+}
+
+#[test]
+fn deposits_are_counted() {
+    let mut env = Env::funded(1_000_000_000);
+
+    env.process(&env.deposit(1));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.total_deposited(), 1_000_000_001);
+    assert_eq!(state.total_withdrawn(), 0);
+}
+
+// DepositAccounts
+
+#[test]
+fn rejects_missing_accounts() {
+    let mut env = Env::initialized();
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.accounts.pop();
+
+    env.expect_err(&instruction, ProgramError::NotEnoughAccountKeys);
+}
+
+#[test]
+fn rejects_extra_accounts() {
+    let mut env = Env::initialized();
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(Pubkey::new_unique(), false));
+
+    env.expect_err(&instruction, ProgramError::NotEnoughAccountKeys);
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_vault_not_owned_by_system_program() {
+    let mut env = Env::initialized();
+    let vault = env.vault;
+    env.set_account(&vault, Account::new(env.rent_exempt(0), 0, &program_id()));
+
+    env.expect_err(
+        &env.deposit(1_000_000_000),
+        vault_error(VaultError::InvalidVaultOwner),
+    );
+}
+
+#[test]
+fn rejects_uninitialized_state() {
+    let mut env = Env::new();
+
+    env.expect_err(
+        &env.deposit(1_000_000_000),
+        vault_error(VaultError::StateNotInitialized),
+    );
+}
+
+#[test]
+fn rejects_state_not_owned_by_program() {
+    let mut env = Env::new();
+    let state = env.state;
+    env.set_account(
+        &state,
+        Account {
+            lamports: env.rent_exempt(VaultState::LEN),
+            data: vec![0; VaultState::LEN],
+            owner: system_program(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    env.expect_err(
+        &env.deposit(1_000_000_000),
+        vault_error(VaultError::InvalidStateAccount),
+    );
+}
+
+#[test]
+fn rejects_state_with_wrong_length() {
+    let mut env = Env::initialized();
+    let state = env.state;
+    let mut account = env.account(&state);
+    account.data.pop();
+    env.set_account(&state, account);
+
+    env.expect_err(
+        &env.deposit(1_000_000_000),
+        vault_error(VaultError::InvalidStateAccount),
+    );
+}
+
+#[test]
+fn rejects_state_of_unknown_version() {
+    let mut env = Env::initialized();
+    let state = env.state;
+    let mut account = env.account(&state);
+    account.data[0] += 1;
+    env.set_account(&state, account);
+
+    env.expect_err(
+        &env.deposit(1_000_000_000),
+        vault_error(VaultError::InvalidStateAccount),
+    );
+}
+
+#[test]
+fn rejects_state_of_another_index() {
+    // The state of vault 8, passed along with vault 7.
+    let mut env = Env::new();
+    env.initialize(INDEX + 1, 0);
+    let other_state = state_address(&vault_address(&env.owner, INDEX + 1));
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.accounts[2].pubkey = other_state;
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidStateAccount));
+}
+
+#[test]
+fn rejects_wrong_vault_address() {
+    let mut env = Env::initialized();
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.accounts[1].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+#[test]
+fn rejects_non_canonical_vault_address() {
+    // A valid PDA of the same seeds, but not with the bump stored at initialization.
+    let mut env = Env::funded(1_000_000_000);
+    let impostor = non_canonical_vault_address(&env.owner, INDEX);
+    env.fund(&impostor, 1_000_000_000);
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.accounts[1].pubkey = impostor;
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::initialized();
+    let intruder = Pubkey::new_unique();
+    env.fund(&intruder, OWNER_LAMPORTS);
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+// DepositInstructionData
+
+#[test]
+fn rejects_missing_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.data.truncate(1);
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn rejects_short_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn rejects_long_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.deposit(1_000_000_000);
+    instruction.data.push(0);
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn rejects_zero_amount() {
+    let mut env = Env::initialized();
+
+    env.expect_err(&env.deposit(0), vault_error(VaultError::ZeroAmount));
+}
+
+// Deposit
+
+#[test]
+fn rejects_first_deposit_below_rent_exemption() {
+    let mut env = Env::initialized();
+    let amount = env.rent_exempt(0) - 1;
+
+    env.expect_err(
+        &env.deposit(amount),
+        vault_error(VaultError::DepositBelowRentExemption),
+    );
+}
+
+#[test]
+fn rejects_deposit_overflowing_the_vault() {
+    let mut env = Env::initialized();
+    let vault = env.vault;
+    env.fund(&vault, u64::MAX - 10);
+
+    env.expect_err(&env.deposit(11), ProgramError::ArithmeticOverflow);
+}
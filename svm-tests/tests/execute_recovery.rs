@@ -0,0 +1,168 @@
+mod common;
+
+use blueshift_vault::{client, RecoveryState, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+const CHALLENGE_PERIOD: i64 = 86_400;
+
+// A funded vault with 2-of-3 guardians and an open proposal handing it to a new owner.
+// Returns the new owner and the payer of the proposal.
+fn proposed_env() -> (Env, Pubkey, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let guardians = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    env.process(&env.set_guardians(2, CHALLENGE_PERIOD, &guardians));
+    let payer = Pubkey::new_unique();
+    env.fund(&payer, OWNER_LAMPORTS);
+    let new_owner = Pubkey::new_unique();
+    env.process(&env.propose_recovery(&payer, &new_owner, &guardians[..2]));
+    (env, new_owner, payer)
+}
+
+#[test]
+fn execute_hands_the_vault_to_the_new_owner() {
+    let (mut env, new_owner, payer) = proposed_env();
+    let recovery = recovery_address(&env.vault);
+    let payer_before = env.lamports(&payer);
+
+    env.warp(CHALLENGE_PERIOD);
+    env.process(&env.execute_recovery(&payer));
+
+    let account = env.account(&env.state);
+    assert_eq!(
+        VaultState::load(&account.data).unwrap().owner(),
+        &new_owner.to_bytes()
+    );
+    // The recovery is closed and its rent goes back to the payer.
+    assert_eq!(env.lamports(&recovery), 0);
+    assert_eq!(
+        env.lamports(&payer),
+        payer_before + env.rent_exempt(RecoveryState::LEN)
+    );
+    assert_eq!(env.open_accounts(), 0);
+
+    // The lost key no longer controls the vault, the new owner does.
+    env.expect_err(&env.withdraw(None), vault_error(VaultError::Unauthorized));
+    env.process(&to_sdk(client::withdraw(
+        &new_owner.to_bytes(),
+        &env.key(),
+        INDEX,
+        None,
+    )));
+    assert_eq!(env.lamports(&new_owner), BALANCE);
+}
+
+#[test]
+fn execute_removes_the_multisig_and_a_pending_transfer() {
+    let mut env = Env::funded(BALANCE);
+    let guardians = [Pubkey::new_unique()];
+    env.process(&env.set_guardians(1, CHALLENGE_PERIOD, &guardians));
+    let member = Pubkey::new_unique();
+    env.process(&to_sdk(client::set_multisig(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        1,
+        &[member.to_bytes()],
+    )));
+    let payer = Pubkey::new_unique();
+    env.fund(&payer, OWNER_LAMPORTS);
+    let new_owner = Pubkey::new_unique();
+    env.process(&env.propose_recovery(&payer, &new_owner, &guardians));
+
+    env.warp(CHALLENGE_PERIOD);
+    env.process(&env.execute_recovery(&payer));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.threshold(), 0);
+    assert!(state.members().is_empty());
+    assert_eq!(state.pending_owner(), &[0; 32]);
+
+    // The new owner's signature alone is enough.
+    env.process(&to_sdk(client::withdraw(
+        &new_owner.to_bytes(),
+        &env.key(),
+        INDEX,
+        None,
+    )));
+}
+
+#[test]
+fn rejects_recovery_not_ready() {
+    let (mut env, _, payer) = proposed_env();
+
+    env.warp(CHALLENGE_PERIOD - 1);
+
+    env.expect_err(
+        &env.execute_recovery(&payer),
+        vault_error(VaultError::RecoveryNotReady),
+    );
+}
+
+#[test]
+fn removing_the_guardians_revokes_the_proposal() {
+    let (mut env, _, payer) = proposed_env();
+    env.process(&env.set_guardians(0, 0, &[]));
+    env.warp(CHALLENGE_PERIOD);
+
+    env.expect_err(
+        &env.execute_recovery(&payer),
+        vault_error(VaultError::InvalidRecovery),
+    );
+
+    // The owner can still close it.
+    env.process(&env.cancel_recovery(&payer));
+    assert_eq!(env.open_accounts(), 0);
+}
+
+// ExecuteRecoveryAccounts
+
+#[test]
+fn rejects_another_payer() {
+    let (mut env, _, _) = proposed_env();
+    env.warp(CHALLENGE_PERIOD);
+
+    env.expect_err(
+        &env.execute_recovery(&Pubkey::new_unique()),
+        vault_error(VaultError::InvalidRecovery),
+    );
+}
+
+#[test]
+fn rejects_without_a_proposal() {
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.set_guardians(1, CHALLENGE_PERIOD, &[Pubkey::new_unique()]));
+
+    env.expect_err(
+        &env.execute_recovery(&Pubkey::new_unique()),
+        vault_error(VaultError::InvalidRecovery),
+    );
+}
+
+#[test]
+fn rejects_wrong_vault_address() {
+    let (mut env, _, payer) = proposed_env();
+    env.warp(CHALLENGE_PERIOD);
+    let mut instruction = env.execute_recovery(&payer);
+    instruction.accounts[0].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+// ExecuteRecoveryInstructionData
+
+#[test]
+fn rejects_malformed_data() {
+    let (mut env, _, payer) = proposed_env();
+    env.warp(CHALLENGE_PERIOD);
+    let mut instruction = env.execute_recovery(&payer);
+    instruction.data.push(0);
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use blueshift_vault::{PendingWithdrawal, VaultError};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+const AMOUNT: u64 = 400_000_000;
+const DELAY: i64 = 3_600;
+
+// A funded vault with a withdrawal of `AMOUNT` to the returned destination queued as request 1.
+fn queued_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.set_withdraw_delay(DELAY));
+    let destination = Pubkey::new_unique();
+    env.process(&env.request_withdraw(&destination, 1, AMOUNT));
+    (env, destination)
+}
+
+#[test]
+fn execute_pays_out_once_the_delay_has_passed() {
+    let (mut env, destination) = queued_env();
+    let pending = pending_withdrawal_address(&env.vault, 1);
+    let owner_before = env.lamports(&env.owner);
+
+    env.warp(DELAY);
+    env.process(&env.execute_withdraw(1, &destination));
+
+    assert_eq!(env.lamports(&destination), AMOUNT);
+    assert_eq!(env.lamports(&env.vault), BALANCE - AMOUNT);
+    // The pending withdrawal is closed and its rent goes back to the owner who paid for it.
+    assert_eq!(env.lamports(&pending), 0);
+    assert_eq!(
+        env.lamports(&env.owner),
+        owner_before + env.rent_exempt(PendingWithdrawal::LEN)
+    );
+    assert_eq!(env.open_accounts(), 0);
+}
+
+#[test]
+fn rejects_withdrawal_not_ready() {
+    let (mut env, destination) = queued_env();
+
+    env.warp(DELAY - 1);
+
+    env.expect_err(
+        &env.execute_withdraw(1, &destination),
+        vault_error(VaultError::WithdrawalNotReady),
+    );
+}
+
+#[test]
+fn rejects_executing_twice() {
+    let (mut env, destination) = queued_env();
+    env.warp(DELAY);
+    env.process(&env.execute_withdraw(1, &destination));
+
+    env.expect_err(
+        &env.execute_withdraw(1, &destination),
+        vault_error(VaultError::InvalidPendingWithdrawal),
+    );
+}
+
+#[test]
+fn rejects_another_destination() {
+    let (mut env, _) = queued_env();
+    env.warp(DELAY);
+
+    env.expect_err(
+        &env.execute_withdraw(1, &Pubkey::new_unique()),
+        vault_error(VaultError::InvalidDestination),
+    );
+}
+
+#[test]
+fn rejects_amount_above_balance_at_execution() {
+    // The balance is only checked when the withdrawal is executed.
+    let (mut env, destination) = queued_env();
+    env.process(&env.request_withdraw(&destination, 2, BALANCE));
+    env.warp(DELAY);
+    env.process(&env.execute_withdraw(1, &destination));
+
+    env.expect_err(
+        &env.execute_withdraw(2, &destination),
+        vault_error(VaultError::InsufficientFunds),
+    );
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let (mut env, destination) = queued_env();
+    env.warp(DELAY);
+    let mut instruction = env.execute_withdraw(1, &destination);
+    instruction.data.push(0);
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use blueshift_vault::{client, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+
+// A funded vault with a freeze authority, which is returned.
+fn freezable_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let freeze_authority = Pubkey::new_unique();
+    env.process(&env.set_freeze_authority(&freeze_authority));
+    (env, freeze_authority)
+}
+
+fn is_frozen(env: &Env) -> bool {
+    let account = env.account(&env.state);
+    VaultState::load(&account.data).unwrap().is_frozen()
+}
+
+#[test]
+fn freeze_halts_withdrawals() {
+    let (mut env, freeze_authority) = freezable_env();
+
+    env.process(&env.freeze(&freeze_authority));
+
+    assert!(is_frozen(&env));
+    env.expect_err(&env.withdraw(None), vault_error(VaultError::VaultFrozen));
+}
+
+#[test]
+fn freeze_halts_configuration_changes() {
+    // A key misused during the incident can't grant itself anything while the vault is frozen.
+    let (mut env, freeze_authority) = freezable_env();
+    let key = Pubkey::new_unique();
+    env.process(&env.freeze(&freeze_authority));
+
+    let instructions = [
+        env.transfer_ownership(&key),
+        to_sdk(client::set_multisig(
+            &env.key(),
+            &env.key(),
+            INDEX,
+            1,
+            &[key.to_bytes()],
+        )),
+        env.add_destination(&key),
+        env.set_inheritance(&key, 60),
+        env.approve(&key, 500, 0, 0),
+        env.set_guardians(1, 60, &[key]),
+        env.set_withdraw_delay(60),
+        to_sdk(client::set_rate_limit(
+            &env.key(),
+            &env.key(),
+            INDEX,
+            BALANCE,
+            0,
+        )),
+        env.request_withdraw(&key, 1, 100_000_000),
+        env.set_freeze_authority(&key),
+    ];
+    for instruction in &instructions {
+        env.expect_err(instruction, vault_error(VaultError::VaultFrozen));
+    }
+}
+
+#[test]
+fn freeze_halts_a_transfer_proposed_before() {
+    let (mut env, freeze_authority) = freezable_env();
+    let new_owner = Pubkey::new_unique();
+    env.process(&env.transfer_ownership(&new_owner));
+    env.process(&env.freeze(&freeze_authority));
+
+    env.expect_err(
+        &env.accept_ownership(&new_owner),
+        vault_error(VaultError::VaultFrozen),
+    );
+}
+
+#[test]
+fn owner_can_still_take_things_away() {
+    let (mut env, freeze_authority) = freezable_env();
+    let delegate = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    env.process(&env.approve(&delegate, 500, 0, 0));
+    env.process(&env.request_withdraw(&destination, 1, 100_000_000));
+    env.process(&env.add_destination(&destination));
+    env.process(&env.freeze(&freeze_authority));
+
+    env.process(&env.deposit(BALANCE));
+    env.process(&env.heartbeat());
+    env.process(&to_sdk(client::revoke(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &delegate.to_bytes(),
+    )));
+    env.process(&env.remove_destination(&destination));
+    env.process(&env.cancel_withdraw(1));
+
+    assert_eq!(env.lamports(&env.vault), 2 * BALANCE);
+}
+
+#[test]
+fn guardians_can_recover_a_frozen_vault() {
+    // The new owner gets the vault as it is: only the freeze authority lifts the freeze.
+    let (mut env, freeze_authority) = freezable_env();
+    let guardian = Pubkey::new_unique();
+    env.process(&env.set_guardians(1, 60, &[guardian]));
+    env.process(&env.freeze(&freeze_authority));
+    let payer = Pubkey::new_unique();
+    env.fund(&payer, OWNER_LAMPORTS);
+    let new_owner = Pubkey::new_unique();
+    env.process(&env.propose_recovery(&payer, &new_owner, &[guardian]));
+    env.warp(60);
+
+    env.process(&env.execute_recovery(&payer));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.owner(), &new_owner.to_bytes());
+    assert!(state.is_frozen());
+}
+
+// FreezeAccounts
+
+#[test]
+fn rejects_signer_not_the_freeze_authority() {
+    // The owner can't freeze its own vault either; only the authority it named can.
+    let (mut env, _) = freezable_env();
+    let owner = env.owner;
+
+    env.expect_err(&env.freeze(&owner), vault_error(VaultError::Unauthorized));
+    assert!(!is_frozen(&env));
+}
+
+#[test]
+fn rejects_vault_without_freeze_authority() {
+    let mut env = Env::funded(BALANCE);
+
+    env.expect_err(
+        &env.freeze(&Pubkey::new_from_array([0; 32])),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn rejects_freeze_authority_not_signer() {
+    let (mut env, freeze_authority) = freezable_env();
+    let mut instruction = env.freeze(&freeze_authority);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_wrong_vault_address() {
+    let (mut env, freeze_authority) = freezable_env();
+    let mut instruction = env.freeze(&freeze_authority);
+    instruction.accounts[1].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+// FreezeInstructionData
+
+#[test]
+fn rejects_malformed_data() {
+    let (mut env, freeze_authority) = freezable_env();
+    let mut instruction = env.freeze(&freeze_authority);
+    instruction.data.push(0);
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
@@ -0,0 +1,186 @@
+// Property-based tests feeding arbitrary instruction data and account lists to the program.
+//
+// The attacker controls every key except the owner's: it can sign for itself and any other
+// account, owns a funded vault of its own with the same index, and can pass accounts in any order
+// with any flags. Whatever it sends, the program must fail cleanly or keep the invariants below.
+
+mod common;
+
+use blueshift_vault::*;
+use common::*;
+use mollusk_svm::result::ProgramResult;
+use proptest::{collection::vec, prelude::*};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+};
+
+const BALANCE: u64 = 1_000_000_000;
+
+#[derive(Clone, Debug)]
+struct FuzzInstruction {
+    data: Vec<u8>,
+    // Index into the account pool, signer and writable flags.
+    accounts: Vec<(usize, bool, bool)>,
+}
+
+// Accounts the fuzzer picks from.
+struct Pool {
+    env: Env,
+    keys: Vec<Pubkey>,
+}
+
+const POOL_LEN: usize = 12;
+
+fn pool() -> Pool {
+    let mut env = Env::funded(BALANCE);
+
+    let attacker_env = Env::funded(BALANCE);
+    let (attacker, attacker_vault, attacker_state) =
+        (attacker_env.owner, attacker_env.vault, attacker_env.state);
+    env.accounts.extend(attacker_env.accounts);
+
+    let keys = vec![
+        env.owner,
+        attacker,
+        env.vault,
+        env.state,
+        attacker_vault,
+        attacker_state,
+        system_program(),
+        allowlist_address(&env.vault),
+        recovery_address(&env.vault),
+        delegate_record_address(&env.vault, &attacker),
+        pending_withdrawal_address(&env.vault, 0),
+        Pubkey::new_unique(),
+    ];
+    assert_eq!(keys.len(), POOL_LEN);
+
+    Pool { env, keys }
+}
+
+// Either random bytes, or a known discriminator followed by the vault index and random bytes,
+// which gets past the first checks of every instruction more often.
+fn instruction_data() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        vec(any::<u8>(), 0..160),
+        (0u8..=30, vec(any::<u8>(), 0..152)).prop_map(|(discriminator, tail)| {
+            let mut data = vec![discriminator];
+            data.extend_from_slice(&INDEX.to_le_bytes());
+            data.extend_from_slice(&tail);
+            data
+        }),
+    ]
+}
+
+fn fuzz_instruction() -> impl Strategy<Value = FuzzInstruction> {
+    (
+        instruction_data(),
+        vec((0..POOL_LEN, any::<bool>(), any::<bool>()), 0..12),
+    )
+        .prop_map(|(data, accounts)| FuzzInstruction { data, accounts })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn arbitrary_instructions_keep_invariants(sequence in vec(fuzz_instruction(), 1..6)) {
+        let Pool { mut env, keys } = pool();
+
+        for fuzzed in &sequence {
+            let instruction = Instruction {
+                program_id: program_id(),
+                accounts: fuzzed
+                    .accounts
+                    .iter()
+                    .map(|&(index, is_signer, is_writable)| AccountMeta {
+                        pubkey: keys[index],
+                        // The owner's key is the one thing the attacker doesn't have.
+                        is_signer: is_signer && keys[index].ne(&env.owner),
+                        is_writable,
+                    })
+                    .collect(),
+                data: fuzzed.data.clone(),
+            };
+
+            let vault_before = env.lamports(&env.vault);
+            let (accounts, result) = env.try_process(&instruction);
+
+            // 1. The program never panics or runs out of compute: every failure is a clean error.
+            prop_assert!(
+                !matches!(
+                    result.program_result,
+                    ProgramResult::UnknownError(
+                        InstructionError::ProgramFailedToComplete
+                            | InstructionError::ComputationalBudgetExceeded
+                    )
+                ),
+                "{:?} aborted with {:?}",
+                instruction,
+                result.program_result
+            );
+            if !matches!(result.program_result, ProgramResult::Success) {
+                continue;
+            }
+
+            // 2. Lamports are only moved around, never created or destroyed.
+            let total_before: u128 = accounts
+                .iter()
+                .map(|(_, account)| account.lamports as u128)
+                .sum();
+            let total_after: u128 = result
+                .resulting_accounts
+                .iter()
+                .map(|(_, account)| account.lamports as u128)
+                .sum();
+            prop_assert_eq!(total_before, total_after, "{:?}", instruction);
+
+            // 3. Whatever leaves the owner's vault only goes to the owner.
+            if env.lamports(&env.vault) < vault_before {
+                for (key, account) in &result.resulting_accounts {
+                    let before = accounts
+                        .iter()
+                        .find(|(input, _)| input.eq(key))
+                        .map_or(0, |(_, account)| account.lamports);
+                    prop_assert!(
+                        account.lamports <= before || key.eq(&env.owner),
+                        "{:?} paid the vault out to {:?}",
+                        instruction,
+                        key
+                    );
+                }
+            }
+        }
+    }
+
+    // The instruction data parsers slice and unwrap their input, so they must reject every
+    // malformed length instead of panicking. They are pure, so this runs on the host.
+    #[test]
+    fn instruction_data_parsers_never_panic(data in vec(any::<u8>(), 0..512)) {
+        let data = data.as_slice();
+        let _ = DepositInstructionData::try_from(data);
+        let _ = WithdrawInstructionData::try_from(data);
+        let _ = InitializeInstructionData::try_from(data);
+        let _ = ApproveInstructionData::try_from(data);
+        let _ = RevokeInstructionData::try_from(data);
+        let _ = SetMultisigInstructionData::try_from(data);
+        let _ = RequestWithdrawInstructionData::try_from(data);
+        let _ = CancelWithdrawInstructionData::try_from(data);
+        let _ = SetWithdrawDelayInstructionData::try_from(data);
+        let _ = SetRateLimitInstructionData::try_from(data);
+        let _ = AddDestinationInstructionData::try_from(data);
+        let _ = RemoveDestinationInstructionData::try_from(data);
+        let _ = TransferOwnershipInstructionData::try_from(data);
+        let _ = AcceptOwnershipInstructionData::try_from(data);
+        let _ = SetGuardiansInstructionData::try_from(data);
+        let _ = ProposeRecoveryInstructionData::try_from(data);
+        let _ = CancelRecoveryInstructionData::try_from(data);
+        let _ = ExecuteRecoveryInstructionData::try_from(data);
+        let _ = HeartbeatInstructionData::try_from(data);
+        let _ = SetInheritanceInstructionData::try_from(data);
+        let _ = CloseInstructionData::try_from(data);
+        let _ = SetFreezeAuthorityInstructionData::try_from(data);
+        let _ = FreezeInstructionData::try_from(data);
+    }
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use blueshift_vault::{client, VaultError};
+use common::*;
+use mollusk_svm::result::ProgramResult;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+const TOTAL: u64 = 4_000_000_000;
+
+// Vesting starts now, with a cliff after 1 000 seconds, and ends after 4 000 seconds.
+const CLIFF: i64 = 1_000;
+const DURATION: i64 = 4_000;
+
+// A vault of `TOTAL` lamports vesting to a separate beneficiary, which is returned.
+fn vesting_env() -> (Env, Pubkey) {
+    let mut env = Env::new();
+    let beneficiary = Pubkey::new_unique();
+    env.process(&env.vesting_instruction(NOW, NOW + CLIFF, NOW + DURATION, &beneficiary));
+    env.process(&env.deposit(TOTAL));
+    (env, beneficiary)
+}
+
+fn get_vested_instruction(env: &Env, viewer: &Pubkey) -> Instruction {
+    to_sdk(client::get_vested(&viewer.to_bytes(), &env.key(), INDEX))
+}
+
+// Runs GetVested for `viewer` and decodes its return data: (vested, withdrawable).
+fn get_vested(env: &mut Env, viewer: &Pubkey) -> (u64, u64) {
+    let (_, result) = env.try_process(&get_vested_instruction(env, viewer));
+    assert!(matches!(result.program_result, ProgramResult::Success));
+
+    let data = result.return_data;
+    assert_eq!(data.len(), 16);
+    (
+        u64::from_le_bytes(data[0..8].try_into().unwrap()),
+        u64::from_le_bytes(data[8..16].try_into().unwrap()),
+    )
+}
+
+#[test]
+fn nothing_vests_before_the_cliff() {
+    let (mut env, _) = vesting_env();
+    let owner = env.owner;
+
+    env.warp(CLIFF - 1);
+
+    assert_eq!(get_vested(&mut env, &owner), (0, 0));
+}
+
+#[test]
+fn the_cliff_releases_everything_vested_since_the_start() {
+    let (mut env, _) = vesting_env();
+    let owner = env.owner;
+
+    env.warp(CLIFF);
+
+    assert_eq!(get_vested(&mut env, &owner), (TOTAL / 4, TOTAL / 4));
+}
+
+#[test]
+fn vests_linearly_and_tracks_withdrawals() {
+    let (mut env, _) = vesting_env();
+    let owner = env.owner;
+
+    env.warp(DURATION / 2);
+    assert_eq!(get_vested(&mut env, &owner), (TOTAL / 2, TOTAL / 2));
+
+    env.process(&env.withdraw(Some(TOTAL / 4)));
+    assert_eq!(get_vested(&mut env, &owner), (TOTAL / 2, TOTAL / 4));
+
+    env.warp(DURATION / 4);
+    assert_eq!(get_vested(&mut env, &owner), (TOTAL * 3 / 4, TOTAL / 2));
+}
+
+#[test]
+fn everything_vests_at_the_end() {
+    let (mut env, _) = vesting_env();
+    let owner = env.owner;
+
+    env.warp(DURATION);
+    assert_eq!(get_vested(&mut env, &owner), (TOTAL, TOTAL));
+
+    env.process(&env.withdraw(None));
+    assert_eq!(get_vested(&mut env, &owner), (TOTAL, 0));
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn beneficiary_can_view_the_schedule() {
+    let (mut env, beneficiary) = vesting_env();
+
+    env.warp(DURATION / 2);
+
+    assert_eq!(get_vested(&mut env, &beneficiary), (TOTAL / 2, TOTAL / 2));
+}
+
+#[test]
+fn vault_without_schedule_is_fully_vested() {
+    let mut env = Env::funded(TOTAL);
+    let owner = env.owner;
+
+    assert_eq!(get_vested(&mut env, &owner), (TOTAL, TOTAL));
+}
+
+// GetVestedAccounts
+
+#[test]
+fn rejects_viewer_not_signer() {
+    let (mut env, _) = vesting_env();
+    let mut instruction = get_vested_instruction(&env, &env.owner);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_stranger() {
+    let (mut env, _) = vesting_env();
+
+    env.expect_err(
+        &get_vested_instruction(&env, &Pubkey::new_unique()),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn rejects_uninitialized_state() {
+    let mut env = Env::new();
+
+    env.expect_err(
+        &get_vested_instruction(&env, &env.owner),
+        vault_error(VaultError::StateNotInitialized),
+    );
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use blueshift_vault::{VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const INACTIVITY_PERIOD: i64 = 60;
+
+// A funded vault whose heir, returned, can claim after `INACTIVITY_PERIOD` seconds of inactivity.
+fn inheritance_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(1_000_000_000);
+    let heir = Pubkey::new_unique();
+    env.process(&env.set_inheritance(&heir, INACTIVITY_PERIOD));
+    (env, heir)
+}
+
+fn last_active(env: &Env) -> i64 {
+    let account = env.account(&env.state);
+    VaultState::load(&account.data).unwrap().last_active()
+}
+
+#[test]
+fn heartbeat_records_activity() {
+    let mut env = Env::initialized();
+    assert_eq!(last_active(&env), NOW);
+
+    env.warp(100);
+    env.process(&env.heartbeat());
+
+    assert_eq!(last_active(&env), NOW + 100);
+}
+
+#[test]
+fn heartbeat_restarts_the_inactivity_period() {
+    let (mut env, heir) = inheritance_env();
+
+    env.warp(INACTIVITY_PERIOD - 1);
+    env.process(&env.heartbeat());
+
+    env.warp(INACTIVITY_PERIOD - 1);
+    env.expect_err(
+        &env.claim(&heir, None),
+        vault_error(VaultError::OwnerStillActive),
+    );
+
+    env.warp(1);
+    env.process(&env.claim(&heir, None));
+}
+
+#[test]
+fn other_owner_instructions_count_as_activity() {
+    let (mut env, heir) = inheritance_env();
+
+    env.warp(INACTIVITY_PERIOD - 1);
+    env.process(&env.withdraw(Some(100_000_000)));
+    assert_eq!(last_active(&env), NOW + INACTIVITY_PERIOD - 1);
+
+    env.warp(1);
+    env.expect_err(
+        &env.claim(&heir, None),
+        vault_error(VaultError::OwnerStillActive),
+    );
+}
+
+// HeartbeatAccounts
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    // Anybody else keeping the vault "alive" would lock the heir out forever.
+    let (mut env, heir) = inheritance_env();
+    let mut instruction = env.heartbeat();
+    instruction.accounts[0].pubkey = heir;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = env.heartbeat();
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_uninitialized_state() {
+    let mut env = Env::new();
+
+    env.expect_err(
+        &env.heartbeat(),
+        vault_error(VaultError::StateNotInitialized),
+    );
+}
+
+// HeartbeatInstructionData
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.heartbeat();
+    instruction.data.push(0);
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
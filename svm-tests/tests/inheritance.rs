@@ -0,0 +1,110 @@
+mod common;
+
+use blueshift_vault::{client, InitializeInstructionData, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+const INACTIVITY_PERIOD: i64 = 60;
+
+// A funded vault whose vesting schedule (already over) names `beneficiary`, and whose
+// inheritance names `heir`.
+fn inheritance_env() -> (Env, Pubkey, Pubkey) {
+    let mut env = Env::new();
+    let beneficiary = Pubkey::new_unique();
+    let heir = Pubkey::new_unique();
+    env.fund(&beneficiary, OWNER_LAMPORTS);
+    env.fund(&heir, OWNER_LAMPORTS);
+
+    env.process(&to_sdk(client::initialize(
+        &env.key(),
+        &InitializeInstructionData {
+            index: INDEX,
+            unlock_ts: 0,
+            start_ts: NOW - 2,
+            cliff_ts: 0,
+            end_ts: NOW - 1,
+            beneficiary: beneficiary.to_bytes(),
+            label: [0; 32],
+        },
+    )));
+    env.process(&env.deposit(BALANCE));
+
+    // Initialize only sets the beneficiary.
+    let state = env.account(&env.state);
+    assert_eq!(VaultState::load(&state.data).unwrap().heir(), &[0; 32]);
+
+    env.process(&to_sdk(client::set_inheritance(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &heir.to_bytes(),
+        INACTIVITY_PERIOD,
+    )));
+
+    (env, beneficiary, heir)
+}
+
+fn claim(env: &Env, signer: &Pubkey) -> Instruction {
+    to_sdk(client::claim(
+        &signer.to_bytes(),
+        &env.key(),
+        &env.key(),
+        INDEX,
+        None,
+    ))
+}
+
+fn get_vested(env: &Env, viewer: &Pubkey) -> Instruction {
+    to_sdk(client::get_vested(&viewer.to_bytes(), &env.key(), INDEX))
+}
+
+#[test]
+fn set_inheritance_leaves_the_beneficiary_unchanged() {
+    let (env, beneficiary, heir) = inheritance_env();
+
+    let state = env.account(&env.state);
+    let state = VaultState::load(&state.data).unwrap();
+    assert_eq!(state.beneficiary(), &beneficiary.to_bytes());
+    assert_eq!(state.heir(), &heir.to_bytes());
+    assert_eq!(state.inactivity_period(), INACTIVITY_PERIOD);
+}
+
+#[test]
+fn only_the_beneficiary_views_the_schedule() {
+    let (mut env, beneficiary, heir) = inheritance_env();
+
+    env.process(&get_vested(&env, &beneficiary));
+    env.expect_err(
+        &get_vested(&env, &heir),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn only_the_heir_claims() {
+    let (mut env, beneficiary, heir) = inheritance_env();
+    env.warp(INACTIVITY_PERIOD);
+
+    env.expect_err(
+        &claim(&env, &beneficiary),
+        vault_error(VaultError::Unauthorized),
+    );
+
+    let heir_before = env.lamports(&heir);
+    env.process(&claim(&env, &heir));
+
+    assert_eq!(env.lamports(&heir), heir_before + BALANCE);
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn heir_waits_for_the_inactivity_period() {
+    let (mut env, _, heir) = inheritance_env();
+    env.warp(INACTIVITY_PERIOD - 1);
+
+    env.expect_err(
+        &claim(&env, &heir),
+        vault_error(VaultError::OwnerStillActive),
+    );
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use blueshift_vault::{client, InitializeInstructionData, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+#[test]
+fn initialize_records_the_unlock_time() {
+    let mut env = Env::new();
+
+    env.initialize(INDEX, NOW + 3_600);
+
+    let account = env.account(&env.state);
+    assert_eq!(account.owner, program_id());
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.unlock_ts(), NOW + 3_600);
+}
+
+#[test]
+fn initialize_records_the_vesting_schedule() {
+    let mut env = Env::new();
+    let beneficiary = Pubkey::new_unique();
+
+    env.process(&env.vesting_instruction(NOW, NOW + 100, NOW + 1_000, &beneficiary));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.start_ts(), NOW);
+    assert_eq!(state.cliff_ts(), NOW + 100);
+    assert_eq!(state.end_ts(), NOW + 1_000);
+    assert_eq!(state.beneficiary(), &beneficiary.to_bytes());
+}
+
+#[test]
+fn initialize_records_the_metadata() {
+    let mut env = Env::new();
+    env.mollusk.sysvars.clock.slot = 42;
+    let mut label = [0; 32];
+    label[..7].copy_from_slice(b"savings");
+
+    env.process(&to_sdk(client::initialize(
+        &env.key(),
+        &InitializeInstructionData {
+            index: INDEX,
+            unlock_ts: 0,
+            start_ts: 0,
+            cliff_ts: 0,
+            end_ts: 0,
+            beneficiary: [0; 32],
+            label,
+        },
+    )));
+
+    let account = env.account(&env.state);
+    assert_eq!(account.data.len(), VaultState::LEN);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.owner(), &env.key());
+    assert_eq!(state.creator(), &env.key());
+    assert_eq!(state.index(), INDEX);
+    assert_eq!(state.created_slot(), 42);
+    assert_eq!(state.label(), &label);
+    assert_eq!(state.total_deposited(), 0);
+    assert_eq!(state.total_withdrawn(), 0);
+}
+
+#[test]
+fn lamports_already_in_the_vault_count_as_deposited() {
+    let mut env = Env::new();
+    let vault = env.vault;
+    env.fund(&vault, 1_000_000_000);
+
+    env.initialize(INDEX, 0);
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.total_deposited(), 1_000_000_000);
+}
+
+#[test]
+fn initialize_stores_the_canonical_bump() {
+    let env = Env::initialized();
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    let (vault, bump) = Pubkey::find_program_address(
+        &[b"vault", env.owner.as_ref(), &INDEX.to_le_bytes()],
+        &program_id(),
+    );
+    assert_eq!(vault, env.vault);
+    assert_eq!(state.bump(), bump);
+}
+
+// InitializeAccounts
+
+#[test]
+fn rejects_missing_accounts() {
+    let mut env = Env::new();
+    let mut instruction = env.initialize_instruction(INDEX, 0);
+    instruction.accounts.pop();
+
+    env.expect_err(&instruction, ProgramError::NotEnoughAccountKeys);
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::new();
+    let mut instruction = env.initialize_instruction(INDEX, 0);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_wrong_vault_address() {
+    let mut env = Env::new();
+    let mut instruction = env.initialize_instruction(INDEX, 0);
+    instruction.accounts[1].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+#[test]
+fn rejects_non_canonical_vault_address() {
+    let mut env = Env::new();
+    let mut instruction = env.initialize_instruction(INDEX, 0);
+    instruction.accounts[1].pubkey = non_canonical_vault_address(&env.owner, INDEX);
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+#[test]
+fn rejects_wrong_state_address() {
+    let mut env = Env::new();
+    let mut instruction = env.initialize_instruction(INDEX, 0);
+    instruction.accounts[2].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidStateAddress));
+}
+
+#[test]
+fn rejects_second_initialize() {
+    // Initializing again would let the owner lift the time lock.
+    let mut env = Env::new();
+    env.initialize(INDEX, NOW + 3_600);
+
+    env.expect_err(
+        &env.initialize_instruction(INDEX, 0),
+        vault_error(VaultError::StateAlreadyInitialized),
+    );
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.unlock_ts(), NOW + 3_600);
+}
+
+// InitializeInstructionData
+
+#[test]
+fn rejects_invalid_schedules() {
+    let mut env = Env::new();
+    let beneficiary = Pubkey::new_unique();
+
+    for (start_ts, cliff_ts, end_ts) in [
+        // A start or cliff without an end.
+        (NOW, 0, 0),
+        (0, NOW, 0),
+        // An end that isn't after the start.
+        (NOW, 0, NOW),
+        (NOW + 1, 0, NOW),
+        // A cliff outside the schedule.
+        (NOW, NOW - 1, NOW + 1_000),
+        (NOW, NOW + 1_001, NOW + 1_000),
+    ] {
+        env.expect_err(
+            &env.vesting_instruction(start_ts, cliff_ts, end_ts, &beneficiary),
+            vault_error(VaultError::InvalidSchedule),
+        );
+    }
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::new();
+    let mut instruction = env.initialize_instruction(INDEX, 0);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
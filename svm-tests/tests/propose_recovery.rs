@@ -0,0 +1,148 @@
+mod common;
+
+use blueshift_vault::{RecoveryState, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const CHALLENGE_PERIOD: i64 = 86_400;
+
+// A vault with 2-of-3 guardians, and a funded payer. Returns the guardians and the payer.
+fn guardian_env() -> (Env, [Pubkey; 3], Pubkey) {
+    let mut env = Env::funded(1_000_000_000);
+    let guardians = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    env.process(&env.set_guardians(2, CHALLENGE_PERIOD, &guardians));
+    let payer = Pubkey::new_unique();
+    env.fund(&payer, OWNER_LAMPORTS);
+    (env, guardians, payer)
+}
+
+#[test]
+fn proposal_opens_a_recovery() {
+    let (mut env, guardians, payer) = guardian_env();
+    let new_owner = Pubkey::new_unique();
+
+    env.process(&env.propose_recovery(&payer, &new_owner, &guardians[1..]));
+
+    let recovery_key = recovery_address(&env.vault);
+    let rent = env.rent_exempt(RecoveryState::LEN);
+    assert_eq!(env.lamports(&recovery_key), rent);
+    assert_eq!(env.lamports(&payer), OWNER_LAMPORTS - rent);
+
+    let account = env.account(&recovery_key);
+    let recovery = RecoveryState::load(&account.data).unwrap();
+    assert_eq!(recovery.vault(), &env.vault.to_bytes());
+    assert_eq!(recovery.new_owner(), &new_owner.to_bytes());
+    assert_eq!(recovery.payer(), &payer.to_bytes());
+    assert_eq!(recovery.ready_ts(), NOW + CHALLENGE_PERIOD);
+
+    // Nothing changes hands until the proposal is executed.
+    let account = env.account(&env.state);
+    assert_eq!(VaultState::load(&account.data).unwrap().owner(), &env.key());
+    assert_eq!(env.open_accounts(), 1);
+}
+
+#[test]
+fn rejects_fewer_guardians_than_the_threshold() {
+    let (mut env, guardians, payer) = guardian_env();
+
+    env.expect_err(
+        &env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[..1]),
+        vault_error(VaultError::NotEnoughSigners),
+    );
+}
+
+#[test]
+fn only_guardians_count_towards_the_quorum() {
+    // Neither a stranger nor a guardian listed twice makes up for a missing guardian.
+    let (mut env, guardians, payer) = guardian_env();
+
+    env.expect_err(
+        &env.propose_recovery(
+            &payer,
+            &Pubkey::new_unique(),
+            &[guardians[0], Pubkey::new_unique()],
+        ),
+        vault_error(VaultError::NotEnoughSigners),
+    );
+    env.expect_err(
+        &env.propose_recovery(&payer, &Pubkey::new_unique(), &[guardians[0], guardians[0]]),
+        vault_error(VaultError::NotEnoughSigners),
+    );
+}
+
+#[test]
+fn guardians_must_sign() {
+    let (mut env, guardians, payer) = guardian_env();
+    let mut instruction = env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[1..]);
+    instruction.accounts.last_mut().unwrap().is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotEnoughSigners));
+}
+
+#[test]
+fn rejects_vault_without_guardians() {
+    let mut env = Env::initialized();
+    let payer = Pubkey::new_unique();
+    env.fund(&payer, OWNER_LAMPORTS);
+
+    env.expect_err(
+        &env.propose_recovery(&payer, &Pubkey::new_unique(), &[Pubkey::new_unique()]),
+        vault_error(VaultError::InvalidGuardians),
+    );
+}
+
+#[test]
+fn rejects_a_second_open_proposal() {
+    let (mut env, guardians, payer) = guardian_env();
+    env.process(&env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[1..]));
+
+    env.expect_err(
+        &env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[1..]),
+        vault_error(VaultError::InvalidRecovery),
+    );
+}
+
+// ProposeRecoveryAccounts
+
+#[test]
+fn rejects_payer_not_signer() {
+    let (mut env, guardians, payer) = guardian_env();
+    let mut instruction = env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[1..]);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_wrong_recovery_address() {
+    let (mut env, guardians, payer) = guardian_env();
+    let mut instruction = env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[1..]);
+    instruction.accounts[3].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidRecovery));
+}
+
+// ProposeRecoveryInstructionData
+
+#[test]
+fn rejects_all_zero_new_owner() {
+    let (mut env, guardians, payer) = guardian_env();
+
+    env.expect_err(
+        &env.propose_recovery(&payer, &Pubkey::new_from_array([0; 32]), &guardians[1..]),
+        vault_error(VaultError::InvalidRecovery),
+    );
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let (mut env, guardians, payer) = guardian_env();
+    let mut instruction = env.propose_recovery(&payer, &Pubkey::new_unique(), &guardians[1..]);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
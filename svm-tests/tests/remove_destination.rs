@@ -0,0 +1,105 @@
+mod common;
+
+use blueshift_vault::{Allowlist, VaultError, ZeroCopyAccount};
+use common::*;
+use solana_sdk::pubkey::Pubkey;
+
+const BALANCE: u64 = 1_000_000_000;
+
+// A funded vault with the returned destination listed and active.
+fn allowlist_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let destination = Pubkey::new_unique();
+    env.process(&env.add_destination(&destination));
+    env.warp(Allowlist::ADD_DELAY);
+    (env, destination)
+}
+
+#[test]
+fn removal_is_immediate() {
+    let (mut env, destination) = allowlist_env();
+
+    env.process(&env.remove_destination(&destination));
+
+    let account = env.account(&allowlist_address(&env.vault));
+    assert!(Allowlist::load(&account.data).unwrap().entries().is_empty());
+    env.expect_err(
+        &env.withdraw_to_allowed(&destination, None),
+        vault_error(VaultError::DestinationNotAllowed),
+    );
+}
+
+#[test]
+fn removal_keeps_the_other_destinations() {
+    let (mut env, destination) = allowlist_env();
+    let other = Pubkey::new_unique();
+    env.process(&env.add_destination(&other));
+    env.warp(Allowlist::ADD_DELAY);
+
+    env.process(&env.remove_destination(&destination));
+    env.process(&env.withdraw_to_allowed(&other, None));
+
+    assert_eq!(env.lamports(&other), BALANCE);
+}
+
+#[test]
+fn removal_cancels_a_pending_addition() {
+    let (mut env, _) = allowlist_env();
+    let pending = Pubkey::new_unique();
+    env.process(&env.add_destination(&pending));
+
+    env.process(&env.remove_destination(&pending));
+    env.warp(Allowlist::ADD_DELAY);
+
+    env.expect_err(
+        &env.withdraw_to_allowed(&pending, None),
+        vault_error(VaultError::DestinationNotAllowed),
+    );
+}
+
+#[test]
+fn rejects_destination_not_listed() {
+    let (mut env, _) = allowlist_env();
+
+    env.expect_err(
+        &env.remove_destination(&Pubkey::new_unique()),
+        vault_error(VaultError::DestinationNotAllowed),
+    );
+}
+
+// RemoveDestinationAccounts
+
+#[test]
+fn rejects_owner_not_signer() {
+    let (mut env, destination) = allowlist_env();
+    let mut instruction = env.remove_destination(&destination);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let (mut env, destination) = allowlist_env();
+    let mut instruction = env.remove_destination(&destination);
+    instruction.accounts[0].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_allowlist_of_another_vault() {
+    let (mut env, destination) = allowlist_env();
+    env.initialize(INDEX + 1, 0);
+    let other_vault = vault_address(&env.owner, INDEX + 1);
+    env.process(&to_sdk(blueshift_vault::client::add_destination(
+        &env.key(),
+        &env.key(),
+        INDEX + 1,
+        &destination.to_bytes(),
+    )));
+    let mut instruction = env.remove_destination(&destination);
+    instruction.accounts[3].pubkey = allowlist_address(&other_vault);
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidAllowlist));
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use blueshift_vault::{PendingWithdrawal, VaultError, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+const DELAY: i64 = 3_600;
+
+// A funded vault with a withdrawal delay.
+fn queue_env() -> Env {
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.set_withdraw_delay(DELAY));
+    env
+}
+
+#[test]
+fn request_records_the_pending_withdrawal() {
+    let mut env = queue_env();
+    let destination = Pubkey::new_unique();
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&env.request_withdraw(&destination, 1, 400_000_000));
+
+    let pending_key = pending_withdrawal_address(&env.vault, 1);
+    let rent = env.rent_exempt(PendingWithdrawal::LEN);
+    assert_eq!(env.lamports(&pending_key), rent);
+    assert_eq!(env.lamports(&env.owner), owner_before - rent);
+
+    let account = env.account(&pending_key);
+    let pending = PendingWithdrawal::load(&account.data).unwrap();
+    assert_eq!(pending.vault(), &env.vault.to_bytes());
+    assert_eq!(pending.destination(), &destination.to_bytes());
+    assert_eq!(pending.amount(), 400_000_000);
+    assert_eq!(pending.ready_ts(), NOW + DELAY);
+
+    // Nothing moves until the withdrawal is executed, but the vault can't be closed meanwhile.
+    assert_eq!(env.lamports(&env.vault), BALANCE);
+    assert_eq!(env.open_accounts(), 1);
+}
+
+#[test]
+fn several_requests_can_be_pending() {
+    let mut env = queue_env();
+    let destination = Pubkey::new_unique();
+
+    env.process(&env.request_withdraw(&destination, 1, 400_000_000));
+    env.process(&env.request_withdraw(&destination, 2, 400_000_000));
+
+    assert_eq!(env.open_accounts(), 2);
+}
+
+// RequestWithdrawAccounts
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = queue_env();
+    let mut instruction = env.request_withdraw(&Pubkey::new_unique(), 1, 400_000_000);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = queue_env();
+    let intruder = Pubkey::new_unique();
+    env.fund(&intruder, OWNER_LAMPORTS);
+    let mut instruction = env.request_withdraw(&intruder, 1, 400_000_000);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_the_vault_as_destination() {
+    let mut env = queue_env();
+    let vault = env.vault;
+
+    env.expect_err(
+        &env.request_withdraw(&vault, 1, 400_000_000),
+        vault_error(VaultError::InvalidDestination),
+    );
+}
+
+#[test]
+fn rejects_pending_address_of_another_request() {
+    let mut env = queue_env();
+    let mut instruction = env.request_withdraw(&Pubkey::new_unique(), 1, 400_000_000);
+    instruction.accounts[4].pubkey = pending_withdrawal_address(&env.vault, 2);
+
+    env.expect_err(
+        &instruction,
+        vault_error(VaultError::InvalidPendingWithdrawal),
+    );
+}
+
+#[test]
+fn rejects_request_id_already_pending() {
+    let mut env = queue_env();
+    let destination = Pubkey::new_unique();
+    env.process(&env.request_withdraw(&destination, 1, 400_000_000));
+
+    env.expect_err(
+        &env.request_withdraw(&destination, 1, 100_000_000),
+        vault_error(VaultError::InvalidPendingWithdrawal),
+    );
+}
+
+// RequestWithdrawInstructionData
+
+#[test]
+fn rejects_zero_amount() {
+    let mut env = queue_env();
+
+    env.expect_err(
+        &env.request_withdraw(&Pubkey::new_unique(), 1, 0),
+        vault_error(VaultError::ZeroAmount),
+    );
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = queue_env();
+    let mut instruction = env.request_withdraw(&Pubkey::new_unique(), 1, 400_000_000);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
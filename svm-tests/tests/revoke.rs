@@ -0,0 +1,96 @@
+mod common;
+
+use blueshift_vault::{client, DelegateRecord, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+fn revoke(env: &Env, delegate: &Pubkey) -> Instruction {
+    to_sdk(client::revoke(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &delegate.to_bytes(),
+    ))
+}
+
+// A funded vault with an allowance for the returned delegate.
+fn delegate_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(1_000_000_000);
+    let delegate = Pubkey::new_unique();
+    env.process(&env.approve(&delegate, 500_000_000, 0, 0));
+    (env, delegate)
+}
+
+#[test]
+fn revoke_closes_the_record_and_refunds_the_owner() {
+    let (mut env, delegate) = delegate_env();
+    let record = delegate_record_address(&env.vault, &delegate);
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&revoke(&env, &delegate));
+
+    assert_eq!(env.lamports(&record), 0);
+    assert_eq!(
+        env.lamports(&env.owner),
+        owner_before + env.rent_exempt(DelegateRecord::LEN)
+    );
+    let account = env.account(&env.state);
+    assert_eq!(VaultState::load(&account.data).unwrap().open_accounts(), 0);
+}
+
+#[test]
+fn revoked_delegate_can_no_longer_withdraw() {
+    let (mut env, delegate) = delegate_env();
+    env.process(&revoke(&env, &delegate));
+
+    env.expect_err(
+        &to_sdk(client::delegated_withdraw(
+            &delegate.to_bytes(),
+            &env.key(),
+            &env.key(),
+            INDEX,
+            &delegate.to_bytes(),
+            Some(1),
+        )),
+        vault_error(VaultError::InvalidDelegateRecord),
+    );
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    // The delegate can't keep its own allowance alive or close it for the rent.
+    let (mut env, delegate) = delegate_env();
+    let mut instruction = revoke(&env, &delegate);
+    instruction.accounts[0].pubkey = delegate;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let (mut env, delegate) = delegate_env();
+    let mut instruction = revoke(&env, &delegate);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_record_of_another_vault() {
+    let (mut env, delegate) = delegate_env();
+    env.initialize(INDEX + 1, 0);
+    let other_vault = vault_address(&env.owner, INDEX + 1);
+    env.process(&to_sdk(client::approve(
+        &env.key(),
+        &env.key(),
+        INDEX + 1,
+        &delegate.to_bytes(),
+        500,
+        0,
+        0,
+    )));
+    let mut instruction = revoke(&env, &delegate);
+    instruction.accounts[3].pubkey = delegate_record_address(&other_vault, &delegate);
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidDelegateRecord));
+}
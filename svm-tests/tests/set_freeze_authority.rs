@@ -0,0 +1,93 @@
+mod common;
+
+use blueshift_vault::{VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+#[test]
+fn set_freeze_authority_records_the_authority() {
+    let mut env = Env::initialized();
+    let freeze_authority = Pubkey::new_unique();
+
+    env.process(&env.set_freeze_authority(&freeze_authority));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.freeze_authority(), &freeze_authority.to_bytes());
+    assert!(!state.is_frozen());
+}
+
+#[test]
+fn replacing_the_authority_locks_out_the_previous_one() {
+    let mut env = Env::initialized();
+    let old_authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    env.process(&env.set_freeze_authority(&old_authority));
+    env.process(&env.set_freeze_authority(&new_authority));
+
+    env.expect_err(
+        &env.freeze(&old_authority),
+        vault_error(VaultError::Unauthorized),
+    );
+    env.process(&env.freeze(&new_authority));
+}
+
+#[test]
+fn all_zero_key_removes_the_authority() {
+    let mut env = Env::initialized();
+    let freeze_authority = Pubkey::new_unique();
+    env.process(&env.set_freeze_authority(&freeze_authority));
+
+    env.process(&env.set_freeze_authority(&Pubkey::new_from_array([0; 32])));
+
+    env.expect_err(
+        &env.freeze(&freeze_authority),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn rejects_frozen_vault() {
+    // Otherwise a compromised owner key could replace the authority and thaw the vault itself.
+    let mut env = Env::initialized();
+    let freeze_authority = Pubkey::new_unique();
+    env.process(&env.set_freeze_authority(&freeze_authority));
+    env.process(&env.freeze(&freeze_authority));
+
+    env.expect_err(
+        &env.set_freeze_authority(&Pubkey::new_unique()),
+        vault_error(VaultError::VaultFrozen),
+    );
+}
+
+// SetFreezeAuthorityAccounts
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::initialized();
+    let stranger = Pubkey::new_unique();
+    let mut instruction = env.set_freeze_authority(&stranger);
+    instruction.accounts[0].pubkey = stranger;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = env.set_freeze_authority(&Pubkey::new_unique());
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+// SetFreezeAuthorityInstructionData
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.set_freeze_authority(&Pubkey::new_unique());
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use blueshift_vault::{client, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+fn set_guardians(
+    env: &Env,
+    threshold: u8,
+    challenge_period: i64,
+    guardians: &[[u8; 32]],
+) -> Instruction {
+    to_sdk(client::set_guardians(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        threshold,
+        challenge_period,
+        guardians,
+    ))
+}
+
+fn guardians() -> [[u8; 32]; 2] {
+    [
+        Pubkey::new_unique().to_bytes(),
+        Pubkey::new_unique().to_bytes(),
+    ]
+}
+
+#[test]
+fn set_guardians_records_them() {
+    let mut env = Env::initialized();
+    let guardians = guardians();
+
+    env.process(&set_guardians(&env, 2, 60, &guardians));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.guardian_threshold(), 2);
+    assert_eq!(state.guardians(), &guardians);
+    assert_eq!(state.challenge_period(), 60);
+}
+
+#[test]
+fn rejects_zero_challenge_period() {
+    // A quorum of guardians could propose and execute a recovery in a single transaction.
+    let mut env = Env::initialized();
+
+    env.expect_err(
+        &set_guardians(&env, 2, 0, &guardians()),
+        vault_error(VaultError::InvalidGuardians),
+    );
+}
+
+#[test]
+fn rejects_negative_challenge_period() {
+    let mut env = Env::initialized();
+
+    env.expect_err(
+        &set_guardians(&env, 2, -1, &guardians()),
+        vault_error(VaultError::InvalidGuardians),
+    );
+}
+
+#[test]
+fn removing_guardians_takes_no_challenge_period() {
+    let mut env = Env::initialized();
+    env.process(&set_guardians(&env, 2, 60, &guardians()));
+
+    env.process(&set_guardians(&env, 0, 0, &[]));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.guardian_threshold(), 0);
+    assert!(state.guardians().is_empty());
+}
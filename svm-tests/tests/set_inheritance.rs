@@ -0,0 +1,96 @@
+mod common;
+
+use blueshift_vault::{VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const INACTIVITY_PERIOD: i64 = 60;
+
+#[test]
+fn set_inheritance_records_the_heir_and_activity() {
+    let mut env = Env::funded(1_000_000_000);
+    let heir = Pubkey::new_unique();
+
+    env.warp(100);
+    env.process(&env.set_inheritance(&heir, INACTIVITY_PERIOD));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.heir(), &heir.to_bytes());
+    assert_eq!(state.inactivity_period(), INACTIVITY_PERIOD);
+    assert_eq!(state.last_active(), NOW + 100);
+}
+
+#[test]
+fn replacing_the_heir_locks_out_the_previous_one() {
+    let mut env = Env::funded(1_000_000_000);
+    let old_heir = Pubkey::new_unique();
+    let new_heir = Pubkey::new_unique();
+    env.process(&env.set_inheritance(&old_heir, INACTIVITY_PERIOD));
+    env.process(&env.set_inheritance(&new_heir, INACTIVITY_PERIOD));
+
+    env.warp(INACTIVITY_PERIOD);
+
+    env.expect_err(
+        &env.claim(&old_heir, None),
+        vault_error(VaultError::Unauthorized),
+    );
+    env.process(&env.claim(&new_heir, None));
+}
+
+#[test]
+fn zero_period_turns_inheritance_off() {
+    let mut env = Env::funded(1_000_000_000);
+    let heir = Pubkey::new_unique();
+    env.process(&env.set_inheritance(&heir, INACTIVITY_PERIOD));
+    env.process(&env.set_inheritance(&heir, 0));
+
+    env.warp(365 * 86_400);
+
+    env.expect_err(
+        &env.claim(&heir, None),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+// SetInheritanceAccounts
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::initialized();
+    let heir = Pubkey::new_unique();
+    let mut instruction = env.set_inheritance(&heir, INACTIVITY_PERIOD);
+    instruction.accounts[0].pubkey = heir;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = env.set_inheritance(&Pubkey::new_unique(), INACTIVITY_PERIOD);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+// SetInheritanceInstructionData
+
+#[test]
+fn rejects_negative_period() {
+    let mut env = Env::initialized();
+
+    env.expect_err(
+        &env.set_inheritance(&Pubkey::new_unique(), -1),
+        vault_error(VaultError::InvalidInactivityPeriod),
+    );
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.set_inheritance(&Pubkey::new_unique(), INACTIVITY_PERIOD);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
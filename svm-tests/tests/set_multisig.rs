@@ -0,0 +1,179 @@
+mod common;
+
+use blueshift_vault::{client, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+fn set_multisig(env: &Env, threshold: u8, members: &[Pubkey]) -> Instruction {
+    let members: Vec<[u8; 32]> = members.iter().map(|member| member.to_bytes()).collect();
+    to_sdk(client::set_multisig(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        threshold,
+        &members,
+    ))
+}
+
+// Appends `signers` as signing remaining accounts.
+fn signed_by(mut instruction: Instruction, signers: &[Pubkey]) -> Instruction {
+    for signer in signers {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(*signer, true));
+    }
+    instruction
+}
+
+// A vault controlled by a 2-of-3 multisig, whose members are returned.
+fn multisig_env() -> (Env, [Pubkey; 3]) {
+    let mut env = Env::initialized();
+    let members = [
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    env.process(&set_multisig(&env, 2, &members));
+    (env, members)
+}
+
+#[test]
+fn set_multisig_records_the_configuration() {
+    let (env, members) = multisig_env();
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.threshold(), 2);
+    assert_eq!(state.members(), &members.map(|member| member.to_bytes()));
+}
+
+#[test]
+fn changes_need_the_current_quorum() {
+    let (mut env, members) = multisig_env();
+    let new_members = [Pubkey::new_unique()];
+
+    // The owner's signature alone no longer counts, and neither does a single member.
+    env.expect_err(
+        &set_multisig(&env, 1, &new_members),
+        vault_error(VaultError::NotEnoughSigners),
+    );
+    env.expect_err(
+        &signed_by(set_multisig(&env, 1, &new_members), &members[..1]),
+        vault_error(VaultError::NotEnoughSigners),
+    );
+
+    env.process(&signed_by(
+        set_multisig(&env, 1, &new_members),
+        &members[1..],
+    ));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.threshold(), 1);
+    assert_eq!(state.members(), &[new_members[0].to_bytes()]);
+}
+
+#[test]
+fn a_member_signing_twice_counts_once() {
+    let (mut env, members) = multisig_env();
+
+    env.expect_err(
+        &signed_by(set_multisig(&env, 0, &[]), &[members[0], members[0]]),
+        vault_error(VaultError::NotEnoughSigners),
+    );
+}
+
+#[test]
+fn members_must_sign() {
+    let (mut env, members) = multisig_env();
+    let mut instruction = signed_by(set_multisig(&env, 0, &[]), &members[..2]);
+    instruction.accounts.last_mut().unwrap().is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotEnoughSigners));
+}
+
+#[test]
+fn quorum_can_remove_the_multisig() {
+    let (mut env, members) = multisig_env();
+
+    env.process(&signed_by(set_multisig(&env, 0, &[]), &members[..2]));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.threshold(), 0);
+    assert!(state.members().is_empty());
+
+    // The owner alone controls the vault again.
+    env.process(&env.deposit(1_000_000_000));
+    env.process(&env.withdraw(None));
+}
+
+// SetMultisigAccounts
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = set_multisig(&env, 1, &[Pubkey::new_unique()]);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::initialized();
+    let intruder = Pubkey::new_unique();
+    let mut instruction = set_multisig(&env, 1, &[intruder]);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+// SetMultisigInstructionData
+
+#[test]
+fn rejects_inconsistent_configurations() {
+    let mut env = Env::initialized();
+    let member = Pubkey::new_unique();
+
+    for (threshold, members) in [
+        // More signatures than members.
+        (2, vec![member]),
+        // A threshold without members, or members without a threshold.
+        (1, vec![]),
+        (0, vec![member]),
+        // The same member twice.
+        (2, vec![member, member]),
+    ] {
+        env.expect_err(
+            &set_multisig(&env, threshold, &members),
+            vault_error(VaultError::InvalidMultisig),
+        );
+    }
+}
+
+#[test]
+fn rejects_more_than_ten_members() {
+    let mut env = Env::initialized();
+    let members: Vec<Pubkey> = (0..=VaultState::MAX_MEMBERS)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+
+    env.expect_err(
+        &set_multisig(&env, 1, &members),
+        ProgramError::InvalidInstructionData,
+    );
+}
+
+#[test]
+fn rejects_truncated_member() {
+    let mut env = Env::initialized();
+    let mut instruction = set_multisig(&env, 1, &[Pubkey::new_unique()]);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
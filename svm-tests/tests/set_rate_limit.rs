@@ -0,0 +1,188 @@
+mod common;
+
+use blueshift_vault::{client, VaultError, VaultState};
+use common::*;
+use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 3_000_000_000;
+const LIMIT: u64 = 300_000_000;
+
+// `NOW` is 80 000 seconds into its UTC day, so the next day starts 6 400 seconds later.
+const UNTIL_NEXT_DAY: i64 = 6_400;
+
+fn set_rate_limit(env: &Env, limit: u64, period: u8) -> Instruction {
+    to_sdk(client::set_rate_limit(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        limit,
+        period,
+    ))
+}
+
+// A funded vault limited to `LIMIT` lamports per day.
+fn limited_env() -> Env {
+    let mut env = Env::funded(BALANCE);
+    env.process(&set_rate_limit(&env, LIMIT, VaultState::PERIOD_DAY));
+    env
+}
+
+fn rate_limit_exceeded(env: &mut Env, amount: u64) {
+    env.expect_err(
+        &env.withdraw(Some(amount)),
+        vault_error(VaultError::RateLimitExceeded),
+    );
+}
+
+#[test]
+fn withdrawals_are_limited_per_window() {
+    let mut env = limited_env();
+
+    env.process(&env.withdraw(Some(200_000_000)));
+    rate_limit_exceeded(&mut env, 100_000_001);
+    env.process(&env.withdraw(Some(100_000_000)));
+
+    assert_eq!(env.lamports(&env.vault), BALANCE - LIMIT);
+}
+
+#[test]
+fn the_limit_resets_when_the_day_rolls_over() {
+    let mut env = limited_env();
+    env.process(&env.withdraw(Some(LIMIT)));
+
+    env.warp(UNTIL_NEXT_DAY - 1);
+    rate_limit_exceeded(&mut env, 1);
+
+    env.warp(1);
+    env.process(&env.withdraw(Some(LIMIT)));
+
+    assert_eq!(env.lamports(&env.vault), BALANCE - 2 * LIMIT);
+}
+
+#[test]
+fn the_limit_resets_when_the_epoch_rolls_over() {
+    let mut env = Env::funded(BALANCE);
+    env.process(&set_rate_limit(&env, LIMIT, VaultState::PERIOD_EPOCH));
+    env.process(&env.withdraw(Some(LIMIT)));
+
+    // A new day doesn't matter for an epoch limit.
+    env.warp(UNTIL_NEXT_DAY);
+    rate_limit_exceeded(&mut env, 1);
+
+    env.mollusk.sysvars.clock.epoch += 1;
+    env.process(&env.withdraw(Some(LIMIT)));
+}
+
+#[test]
+fn a_stricter_limit_applies_immediately() {
+    let mut env = limited_env();
+    env.process(&env.withdraw(Some(200_000_000)));
+
+    env.process(&set_rate_limit(&env, 250_000_000, VaultState::PERIOD_DAY));
+
+    // What was already spent in this window counts against the new limit.
+    rate_limit_exceeded(&mut env, 50_000_001);
+    env.process(&env.withdraw(Some(50_000_000)));
+}
+
+#[test]
+fn a_looser_limit_waits_for_the_next_window() {
+    // Otherwise a stolen key could raise the limit and drain the vault at once.
+    let mut env = limited_env();
+    env.process(&env.withdraw(Some(LIMIT)));
+
+    env.process(&set_rate_limit(&env, 2 * LIMIT, VaultState::PERIOD_DAY));
+    rate_limit_exceeded(&mut env, 1);
+
+    env.warp(UNTIL_NEXT_DAY);
+    env.process(&env.withdraw(Some(2 * LIMIT)));
+    rate_limit_exceeded(&mut env, 1);
+}
+
+#[test]
+fn removing_the_limit_waits_for_the_next_window() {
+    let mut env = limited_env();
+    env.process(&env.withdraw(Some(LIMIT)));
+
+    env.process(&set_rate_limit(&env, 0, VaultState::PERIOD_DAY));
+    rate_limit_exceeded(&mut env, 1);
+
+    env.warp(UNTIL_NEXT_DAY);
+    env.process(&env.withdraw(None));
+
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn switching_the_period_waits_for_the_next_window() {
+    // A per-epoch limit can let more through than the same per-day limit, so it counts as looser.
+    let mut env = limited_env();
+    env.process(&env.withdraw(Some(LIMIT)));
+
+    env.process(&set_rate_limit(&env, LIMIT, VaultState::PERIOD_EPOCH));
+    rate_limit_exceeded(&mut env, 1);
+
+    env.warp(UNTIL_NEXT_DAY);
+    env.process(&env.withdraw(Some(LIMIT)));
+
+    // From then on the window is the epoch, which hasn't changed.
+    env.warp(86_400);
+    rate_limit_exceeded(&mut env, 1);
+}
+
+#[test]
+fn the_limit_applies_to_every_withdrawal_path() {
+    let mut env = limited_env();
+    let destination = Pubkey::new_unique();
+
+    env.process(&to_sdk(client::withdraw_to(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &destination.to_bytes(),
+        Some(LIMIT),
+    )));
+
+    rate_limit_exceeded(&mut env, 1);
+}
+
+// SetRateLimitAccounts
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = set_rate_limit(&env, LIMIT, VaultState::PERIOD_DAY);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = limited_env();
+    let mut instruction = set_rate_limit(&env, 0, VaultState::PERIOD_DAY);
+    instruction.accounts[0].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+// SetRateLimitInstructionData
+
+#[test]
+fn rejects_unknown_period() {
+    let mut env = Env::initialized();
+
+    env.expect_err(
+        &set_rate_limit(&env, LIMIT, 2),
+        vault_error(VaultError::InvalidRateLimit),
+    );
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::initialized();
+    let mut instruction = set_rate_limit(&env, LIMIT, VaultState::PERIOD_DAY);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use blueshift_vault::{PendingWithdrawal, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+const DELAY: i64 = 3_600;
+
+fn withdraw_delay(env: &Env) -> i64 {
+    let account = env.account(&env.state);
+    let now = env.mollusk.sysvars.clock.unix_timestamp;
+    VaultState::load(&account.data).unwrap().withdraw_delay(now)
+}
+
+fn ready_ts(env: &Env, request_id: u64) -> i64 {
+    let account = env.account(&pending_withdrawal_address(&env.vault, request_id));
+    PendingWithdrawal::load(&account.data).unwrap().ready_ts()
+}
+
+#[test]
+fn delay_routes_withdrawals_through_the_queue() {
+    let mut env = Env::funded(BALANCE);
+
+    env.process(&env.set_withdraw_delay(DELAY));
+
+    assert_eq!(withdraw_delay(&env), DELAY);
+    env.expect_err(
+        &env.withdraw(None),
+        vault_error(VaultError::WithdrawalDelayActive),
+    );
+}
+
+#[test]
+fn longer_delay_applies_immediately() {
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.set_withdraw_delay(DELAY));
+
+    env.process(&env.set_withdraw_delay(2 * DELAY));
+    env.process(&env.request_withdraw(&Pubkey::new_unique(), 1, 400_000_000));
+
+    assert_eq!(withdraw_delay(&env), 2 * DELAY);
+    assert_eq!(ready_ts(&env, 1), NOW + 2 * DELAY);
+}
+
+#[test]
+fn shorter_delay_waits_for_the_current_delay() {
+    // Otherwise a stolen key could drop the delay and withdraw at once.
+    let mut env = Env::funded(BALANCE);
+    env.process(&env.set_withdraw_delay(DELAY));
+
+    env.process(&env.set_withdraw_delay(0));
+
+    // Until `pending_delay_ts` the old delay still applies, to withdrawals and requests alike.
+    assert_eq!(withdraw_delay(&env), DELAY);
+    env.warp(DELAY - 1);
+    env.expect_err(
+        &env.withdraw(None),
+        vault_error(VaultError::WithdrawalDelayActive),
+    );
+    env.process(&env.request_withdraw(&Pubkey::new_unique(), 1, 400_000_000));
+    assert_eq!(ready_ts(&env, 1), NOW + 2 * DELAY - 1);
+
+    env.warp(1);
+    assert_eq!(withdraw_delay(&env), 0);
+    env.process(&env.withdraw(None));
+
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+// SetWithdrawDelayAccounts
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = env.set_withdraw_delay(DELAY);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::initialized();
+    let mut instruction = env.set_withdraw_delay(0);
+    instruction.accounts[0].pubkey = Pubkey::new_unique();
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+// SetWithdrawDelayInstructionData
+
+#[test]
+fn rejects_negative_delay() {
+    let mut env = Env::initialized();
+
+    env.expect_err(
+        &env.set_withdraw_delay(-1),
+        vault_error(VaultError::InvalidWithdrawDelay),
+    );
+}
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.set_withdraw_delay(DELAY);
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use blueshift_vault::{VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::pubkey::Pubkey;
+
+const BALANCE: u64 = 1_000_000_000;
+
+// A funded, frozen vault. Returns its freeze authority.
+fn frozen_env() -> (Env, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let freeze_authority = Pubkey::new_unique();
+    env.process(&env.set_freeze_authority(&freeze_authority));
+    env.process(&env.freeze(&freeze_authority));
+    (env, freeze_authority)
+}
+
+#[test]
+fn thaw_resumes_withdrawals_and_configuration() {
+    let (mut env, freeze_authority) = frozen_env();
+
+    env.process(&env.thaw(&freeze_authority));
+
+    let account = env.account(&env.state);
+    assert!(!VaultState::load(&account.data).unwrap().is_frozen());
+    env.process(&env.set_withdraw_delay(0));
+    let owner_before = env.lamports(&env.owner);
+    env.process(&env.withdraw(None));
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE);
+}
+
+// Thaw uses FreezeAccounts
+
+#[test]
+fn rejects_signer_not_the_freeze_authority() {
+    let (mut env, _) = frozen_env();
+    let owner = env.owner;
+
+    env.expect_err(&env.thaw(&owner), vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_freeze_authority_not_signer() {
+    let (mut env, freeze_authority) = frozen_env();
+    let mut instruction = env.thaw(&freeze_authority);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
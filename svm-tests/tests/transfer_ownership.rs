@@ -0,0 +1,90 @@
+mod common;
+
+use blueshift_vault::{VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+
+fn pending_owner(env: &Env) -> [u8; 32] {
+    let account = env.account(&env.state);
+    *VaultState::load(&account.data).unwrap().pending_owner()
+}
+
+#[test]
+fn transfer_only_proposes_the_new_owner() {
+    let mut env = Env::funded(BALANCE);
+    let new_owner = Pubkey::new_unique();
+
+    env.process(&env.transfer_ownership(&new_owner));
+
+    assert_eq!(pending_owner(&env), new_owner.to_bytes());
+    let account = env.account(&env.state);
+    assert_eq!(VaultState::load(&account.data).unwrap().owner(), &env.key());
+
+    // Until the proposal is accepted, the vault is still the current owner's.
+    env.process(&env.withdraw(None));
+}
+
+#[test]
+fn a_new_proposal_replaces_the_previous_one() {
+    let mut env = Env::initialized();
+    let first = Pubkey::new_unique();
+    let second = Pubkey::new_unique();
+    env.process(&env.transfer_ownership(&first));
+
+    env.process(&env.transfer_ownership(&second));
+
+    assert_eq!(pending_owner(&env), second.to_bytes());
+    env.expect_err(
+        &env.accept_ownership(&first),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+#[test]
+fn proposing_nobody_cancels_the_transfer() {
+    let mut env = Env::initialized();
+    let new_owner = Pubkey::new_unique();
+    env.process(&env.transfer_ownership(&new_owner));
+
+    env.process(&env.transfer_ownership(&Pubkey::new_from_array([0; 32])));
+
+    assert_eq!(pending_owner(&env), [0; 32]);
+    env.expect_err(
+        &env.accept_ownership(&new_owner),
+        vault_error(VaultError::Unauthorized),
+    );
+}
+
+// TransferOwnershipAccounts
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::initialized();
+    let mut instruction = env.transfer_ownership(&Pubkey::new_unique());
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::initialized();
+    let intruder = Pubkey::new_unique();
+    let mut instruction = env.transfer_ownership(&intruder);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+// TransferOwnershipInstructionData
+
+#[test]
+fn rejects_malformed_data() {
+    let mut env = Env::initialized();
+    let mut instruction = env.transfer_ownership(&Pubkey::new_unique());
+    instruction.data.pop();
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
@@ -0,0 +1,411 @@
+mod common;
+
+use blueshift_vault::{client, VaultError, VaultState, ZeroCopyAccount};
+use common::*;
+use solana_sdk::{
+    account::Account, instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey,
+};
+
+const BALANCE: u64 = 1_000_000_000;
+
+#[test]
+fn withdraw_without_amount_drains_the_vault() {
+    let mut env = Env::funded(BALANCE);
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&env.withdraw(None));
+
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE);
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn withdraw_with_amount_leaves_the_rest() {
+    let mut env = Env::funded(BALANCE);
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&env.withdraw(Some(400_000_000)));
+
+    assert_eq!(env.lamports(&env.owner), owner_before + 400_000_000);
+    assert_eq!(env.lamports(&env.vault), BALANCE - 400_000_000);
+}
+
+#[test]
+fn withdrawals_are_counted() {
+    let mut env = Env::funded(BALANCE);
+
+    env.process(&env.withdraw(Some(400_000_000)));
+    env.process(&env.withdraw(None));
+
+    let account = env.account(&env.state);
+    let state = VaultState::load(&account.data).unwrap();
+    assert_eq!(state.total_deposited(), BALANCE);
+    assert_eq!(state.total_withdrawn(), BALANCE);
+}
+
+// WithdrawAccounts
+
+#[test]
+fn rejects_missing_accounts() {
+    let mut env = Env::funded(BALANCE);
+    let mut instruction = env.withdraw(None);
+    instruction.accounts.pop();
+
+    env.expect_err(&instruction, ProgramError::NotEnoughAccountKeys);
+}
+
+#[test]
+fn rejects_vault_not_owned_by_system_program() {
+    let mut env = Env::initialized();
+    let vault = env.vault;
+    env.set_account(&vault, Account::new(BALANCE, 0, &program_id()));
+
+    env.expect_err(
+        &env.withdraw(None),
+        vault_error(VaultError::InvalidVaultOwner),
+    );
+}
+
+#[test]
+fn rejects_empty_vault() {
+    let mut env = Env::initialized();
+
+    env.expect_err(&env.withdraw(None), vault_error(VaultError::VaultEmpty));
+}
+
+#[test]
+fn rejects_uninitialized_state() {
+    let mut env = Env::new();
+    let vault = env.vault;
+    env.fund(&vault, BALANCE);
+
+    env.expect_err(
+        &env.withdraw(None),
+        vault_error(VaultError::StateNotInitialized),
+    );
+}
+
+#[test]
+fn rejects_state_not_owned_by_program() {
+    let mut env = Env::funded(BALANCE);
+    let state = env.state;
+    let mut account = env.account(&state);
+    account.owner = system_program();
+    env.set_account(&state, account);
+
+    env.expect_err(
+        &env.withdraw(None),
+        vault_error(VaultError::InvalidStateAccount),
+    );
+}
+
+#[test]
+fn rejects_state_with_wrong_length() {
+    let mut env = Env::funded(BALANCE);
+    let state = env.state;
+    let mut account = env.account(&state);
+    account.data.truncate(VaultState::LEN - 1);
+    env.set_account(&state, account);
+
+    env.expect_err(
+        &env.withdraw(None),
+        vault_error(VaultError::InvalidStateAccount),
+    );
+}
+
+#[test]
+fn rejects_state_of_another_index() {
+    // The state of vault 8, passed along with the funded vault 7.
+    let mut env = Env::funded(BALANCE);
+    env.initialize(INDEX + 1, 0);
+    let other_state = state_address(&vault_address(&env.owner, INDEX + 1));
+    let mut instruction = env.withdraw(None);
+    instruction.accounts[2].pubkey = other_state;
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidStateAccount));
+}
+
+#[test]
+fn rejects_wrong_vault_address() {
+    let mut env = Env::funded(BALANCE);
+    let impostor = Pubkey::new_unique();
+    env.fund(&impostor, BALANCE);
+    let mut instruction = env.withdraw(None);
+    instruction.accounts[1].pubkey = impostor;
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+#[test]
+fn rejects_non_canonical_vault_address() {
+    // A valid PDA of the same seeds, but not with the bump stored at initialization.
+    let mut env = Env::funded(BALANCE);
+    let impostor = non_canonical_vault_address(&env.owner, INDEX);
+    env.fund(&impostor, BALANCE);
+    let mut instruction = env.withdraw(None);
+    instruction.accounts[1].pubkey = impostor;
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    let mut env = Env::funded(BALANCE);
+    let intruder = Pubkey::new_unique();
+    env.fund(&intruder, OWNER_LAMPORTS);
+    let mut instruction = env.withdraw(None);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::funded(BALANCE);
+    let mut instruction = env.withdraw(None);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn multisig_needs_a_quorum() {
+    let mut env = Env::funded(BALANCE);
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    env.process(&to_sdk(client::set_multisig(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        2,
+        &members.map(|member| member.to_bytes()),
+    )));
+
+    // The owner's signature alone no longer counts, and neither does a single member.
+    let mut instruction = env.withdraw(None);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(members[0], true));
+    env.expect_err(&instruction, vault_error(VaultError::NotEnoughSigners));
+
+    let owner_before = env.lamports(&env.owner);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(members[1], true));
+    env.process(&instruction);
+
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE);
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn rejects_withdraw_with_delay() {
+    let mut env = Env::funded(BALANCE);
+    env.process(&to_sdk(client::set_withdraw_delay(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        3_600,
+    )));
+
+    env.expect_err(
+        &env.withdraw(None),
+        vault_error(VaultError::WithdrawalDelayActive),
+    );
+}
+
+#[test]
+fn rejects_locked_vault_until_unlock() {
+    let mut env = Env::new();
+    env.initialize(INDEX, NOW + 3_600);
+    env.process(&env.deposit(BALANCE));
+
+    env.expect_err(&env.withdraw(None), vault_error(VaultError::VaultLocked));
+
+    env.warp(3_600);
+    let owner_before = env.lamports(&env.owner);
+    env.process(&env.withdraw(None));
+
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE);
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn unlocks_at_the_unlock_time() {
+    // The lock ends at `unlock_ts` itself, not a second later.
+    let mut env = Env::new();
+    env.initialize(INDEX, NOW);
+    env.process(&env.deposit(BALANCE));
+
+    env.process(&env.withdraw(None));
+
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn rejects_amount_above_vested() {
+    // Half of the schedule has elapsed, so half of the deposits have vested.
+    let mut env = Env::new();
+    let beneficiary = Pubkey::new_unique();
+    env.process(&env.vesting_instruction(NOW, 0, NOW + 1_000, &beneficiary));
+    env.process(&env.deposit(BALANCE));
+    env.warp(500);
+
+    env.expect_err(
+        &env.withdraw(Some(BALANCE / 2 + 1)),
+        vault_error(VaultError::NotVested),
+    );
+    env.expect_err(&env.withdraw(None), vault_error(VaultError::NotVested));
+
+    let owner_before = env.lamports(&env.owner);
+    env.process(&env.withdraw(Some(BALANCE / 2)));
+
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE / 2);
+}
+
+#[test]
+fn rejects_frozen_vault() {
+    let mut env = Env::funded(BALANCE);
+    let freeze_authority = Pubkey::new_unique();
+    env.process(&to_sdk(client::set_freeze_authority(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &freeze_authority.to_bytes(),
+    )));
+    env.process(&to_sdk(client::freeze(
+        &freeze_authority.to_bytes(),
+        &env.key(),
+        INDEX,
+    )));
+
+    env.expect_err(&env.withdraw(None), vault_error(VaultError::VaultFrozen));
+}
+
+#[test]
+fn allowlist_must_be_passed_and_allow_the_owner() {
+    let mut env = Env::funded(BALANCE);
+    let allowlist = allowlist_address(&env.vault);
+    env.process(&to_sdk(client::add_destination(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &env.key(),
+    )));
+
+    // Without the allowlist account.
+    env.expect_err(&env.withdraw(None), ProgramError::NotEnoughAccountKeys);
+
+    // With it, the owner isn't an active destination until the 24-hour timelock has passed.
+    let mut instruction = env.withdraw(None);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(allowlist, false));
+    env.expect_err(&instruction, vault_error(VaultError::DestinationNotAllowed));
+
+    env.warp(24 * 60 * 60);
+    let owner_before = env.lamports(&env.owner);
+    env.process(&instruction);
+
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE);
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+// WithdrawInstructionData
+
+#[test]
+fn rejects_missing_index() {
+    let mut env = Env::funded(BALANCE);
+    let mut instruction = env.withdraw(None);
+    instruction.data.truncate(5);
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn rejects_malformed_amount() {
+    let mut env = Env::funded(BALANCE);
+    let mut instruction = env.withdraw(Some(1));
+    instruction.data.push(0);
+
+    env.expect_err(&instruction, ProgramError::InvalidInstructionData);
+}
+
+#[test]
+fn rejects_zero_amount() {
+    let mut env = Env::funded(BALANCE);
+
+    env.expect_err(&env.withdraw(Some(0)), vault_error(VaultError::ZeroAmount));
+}
+
+// Withdraw
+
+#[test]
+fn rejects_amount_above_balance() {
+    let mut env = Env::funded(BALANCE);
+
+    env.expect_err(
+        &env.withdraw(Some(BALANCE + 1)),
+        vault_error(VaultError::InsufficientFunds),
+    );
+}
+
+#[test]
+fn rejects_amount_leaving_dust() {
+    let mut env = Env::funded(BALANCE);
+
+    env.expect_err(
+        &env.withdraw(Some(BALANCE - 1)),
+        vault_error(VaultError::WithdrawLeavesDust),
+    );
+}
+
+// LegacyWithdraw
+
+// Lamports sent to `["vault", owner]` before vaults were indexed, when the vault was a bare PDA.
+fn legacy_env() -> (Env, Pubkey) {
+    let mut env = Env::new();
+    let vault = legacy_vault_address(&env.owner);
+    env.fund(&vault, BALANCE);
+    (env, vault)
+}
+
+#[test]
+fn legacy_withdraw_drains_a_vault_without_state() {
+    let (mut env, vault) = legacy_env();
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&to_sdk(client::legacy_withdraw(&env.key())));
+
+    assert_eq!(env.lamports(&env.owner), owner_before + BALANCE);
+    assert_eq!(env.lamports(&vault), 0);
+}
+
+#[test]
+fn legacy_withdraw_rejects_signer_not_the_owner() {
+    let (mut env, _) = legacy_env();
+    let intruder = Pubkey::new_unique();
+    env.fund(&intruder, OWNER_LAMPORTS);
+    let mut instruction = to_sdk(client::legacy_withdraw(&env.key()));
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
+
+#[test]
+fn legacy_withdraw_rejects_owner_not_signer() {
+    let (mut env, _) = legacy_env();
+    let mut instruction = to_sdk(client::legacy_withdraw(&env.key()));
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn legacy_withdraw_rejects_an_indexed_vault() {
+    let mut env = Env::funded(BALANCE);
+    let mut instruction = to_sdk(client::legacy_withdraw(&env.key()));
+    instruction.accounts[1].pubkey = env.vault;
+
+    env.expect_err(&instruction, vault_error(VaultError::InvalidVaultAddress));
+}
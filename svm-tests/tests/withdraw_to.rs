@@ -0,0 +1,95 @@
+mod common;
+
+use blueshift_vault::{client, VaultError};
+use common::*;
+use solana_sdk::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000;
+
+fn withdraw_to(env: &Env, destination: &Pubkey, amount: Option<u64>) -> Instruction {
+    to_sdk(client::withdraw_to(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &destination.to_bytes(),
+        amount,
+    ))
+}
+
+#[test]
+fn withdraw_to_credits_the_destination() {
+    let mut env = Env::funded(BALANCE);
+    let destination = Pubkey::new_unique();
+    let owner_before = env.lamports(&env.owner);
+
+    env.process(&withdraw_to(&env, &destination, None));
+
+    assert_eq!(env.lamports(&destination), BALANCE);
+    assert_eq!(env.lamports(&env.owner), owner_before);
+    assert_eq!(env.lamports(&env.vault), 0);
+}
+
+#[test]
+fn withdraw_to_with_amount_leaves_the_rest() {
+    let mut env = Env::funded(BALANCE);
+    let destination = Pubkey::new_unique();
+    env.fund(&destination, 1);
+
+    env.process(&withdraw_to(&env, &destination, Some(400_000_000)));
+
+    assert_eq!(env.lamports(&destination), 400_000_001);
+    assert_eq!(env.lamports(&env.vault), BALANCE - 400_000_000);
+}
+
+#[test]
+fn rejects_missing_accounts() {
+    let mut env = Env::funded(BALANCE);
+    let mut instruction = withdraw_to(&env, &Pubkey::new_unique(), None);
+    instruction.accounts.pop();
+
+    env.expect_err(&instruction, ProgramError::NotEnoughAccountKeys);
+}
+
+#[test]
+fn rejects_owner_not_signer() {
+    let mut env = Env::funded(BALANCE);
+    let mut instruction = withdraw_to(&env, &Pubkey::new_unique(), None);
+    instruction.accounts[0].is_signer = false;
+
+    env.expect_err(&instruction, vault_error(VaultError::NotSigner));
+}
+
+#[test]
+fn rejects_signer_not_the_owner() {
+    // Anyone can name a destination, so only the owner may sign for it.
+    let mut env = Env::funded(BALANCE);
+    let intruder = Pubkey::new_unique();
+    env.fund(&intruder, OWNER_LAMPORTS);
+    let mut instruction = withdraw_to(&env, &intruder, None);
+    instruction.accounts[0].pubkey = intruder;
+
+    env.expect_err(&instruction, vault_error(VaultError::Unauthorized));
+}
+
+#[test]
+fn rejects_the_vault_as_destination() {
+    let mut env = Env::funded(BALANCE);
+    let vault = env.vault;
+
+    env.expect_err(
+        &withdraw_to(&env, &vault, None),
+        vault_error(VaultError::InvalidDestination),
+    );
+}
+
+#[test]
+fn rejects_locked_vault() {
+    let mut env = Env::new();
+    env.initialize(INDEX, NOW + 3_600);
+    env.process(&env.deposit(BALANCE));
+
+    env.expect_err(
+        &withdraw_to(&env, &Pubkey::new_unique(), None),
+        vault_error(VaultError::VaultLocked),
+    );
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use blueshift_vault::{client, token::TOKEN_PROGRAM_ID, VaultError};
+use common::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const BALANCE: u64 = 1_000_000_000;
+const TOKENS: u64 = 1_000;
+
+fn token_env() -> (Env, Pubkey, Pubkey) {
+    let mut env = Env::funded(BALANCE);
+    let (mint, owner_token_account) = env.add_tokens(0, TOKENS);
+    (env, mint, owner_token_account)
+}
+
+fn withdraw_token(env: &Env, mint: &Pubkey, owner_token_account: &Pubkey) -> Instruction {
+    to_sdk(client::withdraw_token(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &mint.to_bytes(),
+        &owner_token_account.to_bytes(),
+        &TOKEN_PROGRAM_ID,
+        None,
+    ))
+}
+
+#[test]
+fn withdraw_token_drains_the_vault_token_account() {
+    let (mut env, mint, owner_token_account) = token_env();
+
+    env.process(&withdraw_token(&env, &mint, &owner_token_account));
+
+    assert_eq!(env.token_amount(&owner_token_account), TOKENS);
+    assert_eq!(env.token_amount(&vault_token_account(&env.vault, &mint)), 0);
+}
+
+#[test]
+fn rejects_token_account_of_another_wallet() {
+    let (mut env, mint, _) = token_env();
+    let stranger_token_account = Pubkey::new_unique();
+    env.set_token_account(&stranger_token_account, &mint, &Pubkey::new_unique(), 0);
+
+    env.expect_err(
+        &withdraw_token(&env, &mint, &stranger_token_account),
+        vault_error(VaultError::InvalidTokenAccount),
+    );
+}
+
+#[test]
+fn rejects_locked_vault_until_unlock() {
+    let mut env = Env::new();
+    env.initialize(INDEX, NOW + 3_600);
+    let (mint, owner_token_account) = env.add_tokens(0, TOKENS);
+
+    env.expect_err(
+        &withdraw_token(&env, &mint, &owner_token_account),
+        vault_error(VaultError::VaultLocked),
+    );
+
+    env.warp(3_600);
+    env.process(&withdraw_token(&env, &mint, &owner_token_account));
+
+    assert_eq!(env.token_amount(&owner_token_account), TOKENS);
+}
+
+#[test]
+fn rejects_tokens_until_the_schedule_ends() {
+    // The schedule only tracks lamports, so tokens stay in the vault until it ends.
+    let mut env = Env::new();
+    env.process(&env.vesting_instruction(NOW, 0, NOW + 1_000, &Pubkey::new_unique()));
+    let (mint, owner_token_account) = env.add_tokens(0, TOKENS);
+    env.warp(999);
+
+    env.expect_err(
+        &withdraw_token(&env, &mint, &owner_token_account),
+        vault_error(VaultError::NotVested),
+    );
+
+    env.warp(1);
+    env.process(&withdraw_token(&env, &mint, &owner_token_account));
+
+    assert_eq!(env.token_amount(&owner_token_account), TOKENS);
+}
+
+#[test]
+fn allowlist_must_be_passed_and_allow_the_owner() {
+    let (mut env, mint, owner_token_account) = token_env();
+    let allowlist = allowlist_address(&env.vault);
+    env.process(&to_sdk(client::add_destination(
+        &env.key(),
+        &env.key(),
+        INDEX,
+        &env.key(),
+    )));
+
+    // Without the allowlist account.
+    env.expect_err(
+        &withdraw_token(&env, &mint, &owner_token_account),
+        ProgramError::NotEnoughAccountKeys,
+    );
+
+    // With it, the owner isn't an active destination until the 24-hour timelock has passed.
+    let mut instruction = withdraw_token(&env, &mint, &owner_token_account);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(allowlist, false));
+    env.expect_err(&instruction, vault_error(VaultError::DestinationNotAllowed));
+
+    env.warp(24 * 60 * 60);
+    env.process(&instruction);
+
+    assert_eq!(env.token_amount(&owner_token_account), TOKENS);
+}
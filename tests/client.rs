@@ -0,0 +1,519 @@
+// Known-answer tests for the client's PDA derivation. The expected addresses and bumps were
+// computed outside this crate with the runtime's algorithm (SHA-256 of the seeds, bump and program
+// ID, then the ed25519 curve check), so a bug in the host SHA-256 or curve check shows up as a
+// mismatch here instead of as wrong vault addresses in an off-chain service. `svm-tests` compares
+// the same helpers with the SDK's `Pubkey::find_program_address` directly.
+//
+// The builders are checked against the program's own instruction data parsers, so the two can't
+// drift apart.
+
+use blueshift_vault::{client::*, *};
+use pinocchio::pubkey::Pubkey;
+use pinocchio_pubkey::from_str;
+
+const CREATOR: Pubkey = [7; 32];
+const DELEGATE: Pubkey = [9; 32];
+
+// `["vault", CREATOR, 0]`
+const VAULT: Pubkey = from_str("3rs2AYB7Yme9Sjr3gdzCsdqzajnuSpzV4dGdMCKeZpV4");
+
+#[test]
+fn vault_address_matches_the_runtime() {
+    assert_eq!(vault_address(&CREATOR, 0), Some((VAULT, 253)));
+}
+
+#[test]
+fn vault_address_with_a_low_bump() {
+    // Bumps 255 to 253 all hash onto the curve for index 2.
+    assert_eq!(
+        vault_address(&CREATOR, 2),
+        Some((
+            from_str("DqLyr8V33mQXUwd5URgakcqk1zXN62eMU2JxC6f7i56D"),
+            252
+        ))
+    );
+    for bump in 253..=255 {
+        assert_eq!(
+            create_program_address(
+                &[b"vault", &CREATOR, &2u64.to_le_bytes(), &[bump]],
+                &blueshift_vault::ID
+            ),
+            None
+        );
+    }
+}
+
+#[test]
+fn legacy_vault_address_matches_the_runtime() {
+    assert_eq!(
+        legacy_vault_address(&CREATOR),
+        Some((
+            from_str("CvpFWFXFcVriiYpc52mooHmToEpFNoPthJ1k4KKveibX"),
+            255
+        ))
+    );
+}
+
+#[test]
+fn state_address_matches_the_runtime() {
+    assert_eq!(
+        state_address(&VAULT),
+        Some((
+            from_str("8dhYtY7YrLyZLcwnHPKW7A5g4kvnGRMrbwxYdSWyxP4L"),
+            252
+        ))
+    );
+}
+
+#[test]
+fn delegate_record_address_matches_the_runtime() {
+    assert_eq!(
+        delegate_record_address(&VAULT, &DELEGATE),
+        Some((
+            from_str("HVDbkpk2T29vwTkHAbkZ6Rqkg7mKnQ1WDWkXFKJSbdUo"),
+            254
+        ))
+    );
+}
+
+#[test]
+fn pending_withdrawal_address_matches_the_runtime() {
+    assert_eq!(
+        pending_withdrawal_address(&VAULT, 3),
+        Some((
+            from_str("ApGpaZcTqvXfBo8Y31DmXLFUhzm5RKbCiaESxQPkGzgW"),
+            253
+        ))
+    );
+}
+
+#[test]
+fn allowlist_address_matches_the_runtime() {
+    assert_eq!(
+        allowlist_address(&VAULT),
+        Some((
+            from_str("EdTHaNoyDSwh5JN11d29hwuY9Fd8p9BNAgG72sRN5dzY"),
+            255
+        ))
+    );
+}
+
+#[test]
+fn recovery_address_matches_the_runtime() {
+    assert_eq!(
+        recovery_address(&VAULT),
+        Some((
+            from_str("Eht6ZBPr5psvECzXEAW3qaFaX1TeLvYpGW9Yyh2RfzCm"),
+            255
+        ))
+    );
+}
+
+// Builders
+
+const OWNER: Pubkey = [1; 32];
+const INDEX: u64 = 7;
+
+// Splits the builder's data into its discriminator and the part the program's parser sees.
+fn split(instruction: &Instruction, discriminator: &u8) -> Vec<u8> {
+    assert_eq!(instruction.program_id, blueshift_vault::ID);
+    let (first, data) = instruction.data.split_first().unwrap();
+    assert_eq!(first, discriminator);
+    data.to_vec()
+}
+
+fn vault_and_state() -> (Pubkey, Pubkey) {
+    let (vault, _) = vault_address(&OWNER, INDEX).unwrap();
+    (vault, state_address(&vault).unwrap().0)
+}
+
+#[test]
+fn vault_builders_address_the_vault_and_state() {
+    let (vault, state) = vault_and_state();
+    let instructions = [
+        deposit(&OWNER, &OWNER, INDEX, 1),
+        withdraw(&OWNER, &OWNER, INDEX, None),
+        withdraw_to(&OWNER, &OWNER, INDEX, &DELEGATE, None),
+        approve(&OWNER, &OWNER, INDEX, &DELEGATE, 1, 0, 0),
+        revoke(&OWNER, &OWNER, INDEX, &DELEGATE),
+        request_withdraw(&OWNER, &OWNER, INDEX, &DELEGATE, 0, 1),
+        cancel_withdraw(&OWNER, &OWNER, INDEX, 0),
+        set_multisig(&OWNER, &OWNER, INDEX, 0, &[]),
+        add_destination(&OWNER, &OWNER, INDEX, &DELEGATE),
+        heartbeat(&OWNER, &OWNER, INDEX),
+        close(&OWNER, &OWNER, INDEX, &[], &[]),
+    ];
+
+    for instruction in instructions {
+        let instruction = instruction.unwrap();
+        assert_eq!(instruction.accounts[0].pubkey, OWNER);
+        assert!(instruction.accounts[0].is_signer);
+        assert_eq!(instruction.accounts[1].pubkey, vault);
+        assert_eq!(instruction.accounts[2].pubkey, state);
+        assert!(instruction.accounts[2].is_writable);
+        assert_eq!(&instruction.data[1..9], &INDEX.to_le_bytes());
+    }
+}
+
+#[test]
+fn withdraw_builders_match_the_parser() {
+    for amount in [None, Some(5)] {
+        for (instruction, discriminator) in [
+            (
+                withdraw(&OWNER, &OWNER, INDEX, amount),
+                Withdraw::DISCRIMINATOR,
+            ),
+            (
+                withdraw_to(&OWNER, &OWNER, INDEX, &DELEGATE, amount),
+                WithdrawTo::DISCRIMINATOR,
+            ),
+            (
+                delegated_withdraw(&DELEGATE, &OWNER, &OWNER, INDEX, &DELEGATE, amount),
+                DelegatedWithdraw::DISCRIMINATOR,
+            ),
+            (
+                claim(&DELEGATE, &OWNER, &OWNER, INDEX, amount),
+                Claim::DISCRIMINATOR,
+            ),
+            (
+                withdraw_token(
+                    &OWNER,
+                    &OWNER,
+                    INDEX,
+                    &[3; 32],
+                    &[4; 32],
+                    &token::TOKEN_PROGRAM_ID,
+                    amount,
+                ),
+                WithdrawToken::DISCRIMINATOR,
+            ),
+        ] {
+            let data = split(&instruction.unwrap(), discriminator);
+            let parsed = WithdrawInstructionData::try_from(data.as_slice()).unwrap();
+            assert_eq!((parsed.index, parsed.amount), (INDEX, amount));
+        }
+    }
+}
+
+#[test]
+fn legacy_withdraw_builder_sends_no_index() {
+    let instruction = legacy_withdraw(&OWNER).unwrap();
+    assert!(split(&instruction, Withdraw::DISCRIMINATOR).is_empty());
+    assert_eq!(
+        instruction.accounts[1].pubkey,
+        legacy_vault_address(&OWNER).unwrap().0
+    );
+}
+
+#[test]
+fn deposit_builders_match_the_parser() {
+    for (instruction, discriminator) in [
+        (deposit(&OWNER, &OWNER, INDEX, 5), Deposit::DISCRIMINATOR),
+        (
+            deposit_token(
+                &OWNER,
+                &OWNER,
+                INDEX,
+                &[3; 32],
+                &[4; 32],
+                &token::TOKEN_PROGRAM_ID,
+                5,
+            ),
+            DepositToken::DISCRIMINATOR,
+        ),
+    ] {
+        let data = split(&instruction.unwrap(), discriminator);
+        let parsed = DepositInstructionData::try_from(data.as_slice()).unwrap();
+        assert_eq!((parsed.index, parsed.amount), (INDEX, 5));
+    }
+}
+
+#[test]
+fn initialize_builder_matches_the_parser() {
+    let instruction = initialize(
+        &OWNER,
+        &InitializeInstructionData {
+            index: INDEX,
+            unlock_ts: 1,
+            start_ts: 10,
+            cliff_ts: 15,
+            end_ts: 20,
+            beneficiary: DELEGATE,
+            label: [5; 32],
+        },
+    )
+    .unwrap();
+
+    let data = split(&instruction, Initialize::DISCRIMINATOR);
+    let parsed = InitializeInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(
+        (
+            parsed.index,
+            parsed.unlock_ts,
+            parsed.start_ts,
+            parsed.cliff_ts,
+            parsed.end_ts
+        ),
+        (INDEX, 1, 10, 15, 20)
+    );
+    assert_eq!((parsed.beneficiary, parsed.label), (DELEGATE, [5; 32]));
+    assert_eq!(instruction.accounts[1].pubkey, vault_and_state().0);
+}
+
+#[test]
+fn delegate_builders_match_the_parser() {
+    let (vault, _) = vault_and_state();
+    let (record, _) = delegate_record_address(&vault, &DELEGATE).unwrap();
+
+    let instruction = approve(&OWNER, &OWNER, INDEX, &DELEGATE, 100, 50, 10).unwrap();
+    let data = split(&instruction, Approve::DISCRIMINATOR);
+    let parsed = ApproveInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(
+        (
+            parsed.index,
+            parsed.allowance,
+            parsed.expiry_slot,
+            parsed.per_tx_cap
+        ),
+        (INDEX, 100, 50, 10)
+    );
+    assert_eq!(instruction.accounts[4].pubkey, record);
+
+    let instruction = revoke(&OWNER, &OWNER, INDEX, &DELEGATE).unwrap();
+    let data = split(&instruction, Revoke::DISCRIMINATOR);
+    assert_eq!(
+        RevokeInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .index,
+        INDEX
+    );
+    assert_eq!(instruction.accounts[3].pubkey, record);
+
+    let instruction = delegated_withdraw(&DELEGATE, &OWNER, &OWNER, INDEX, &[4; 32], None).unwrap();
+    assert_eq!(instruction.accounts[4].pubkey, record);
+}
+
+#[test]
+fn queue_builders_match_the_parser() {
+    let (vault, _) = vault_and_state();
+    let (pending, _) = pending_withdrawal_address(&vault, 3).unwrap();
+
+    let instruction = request_withdraw(&OWNER, &OWNER, INDEX, &DELEGATE, 3, 100).unwrap();
+    let data = split(&instruction, RequestWithdraw::DISCRIMINATOR);
+    let parsed = RequestWithdrawInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(
+        (parsed.index, parsed.request_id, parsed.amount),
+        (INDEX, 3, 100)
+    );
+    assert_eq!(instruction.accounts[4].pubkey, pending);
+
+    let instruction = execute_withdraw(&OWNER, &OWNER, INDEX, 3, &DELEGATE).unwrap();
+    assert_eq!(
+        split(&instruction, ExecuteWithdraw::DISCRIMINATOR),
+        INDEX.to_le_bytes()
+    );
+    assert_eq!(instruction.accounts[3].pubkey, pending);
+
+    let instruction = cancel_withdraw(&OWNER, &OWNER, INDEX, 3).unwrap();
+    let data = split(&instruction, CancelWithdraw::DISCRIMINATOR);
+    assert_eq!(
+        CancelWithdrawInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .index,
+        INDEX
+    );
+    assert_eq!(instruction.accounts[3].pubkey, pending);
+}
+
+#[test]
+fn configuration_builders_match_the_parser() {
+    let members = [[3; 32], [4; 32]];
+
+    let data = split(
+        &set_multisig(&OWNER, &OWNER, INDEX, 2, &members).unwrap(),
+        SetMultisig::DISCRIMINATOR,
+    );
+    let parsed = SetMultisigInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(
+        (parsed.index, parsed.threshold, parsed.member_count),
+        (INDEX, 2, 2)
+    );
+    assert_eq!(parsed.members[..2], members);
+
+    let data = split(
+        &set_withdraw_delay(&OWNER, &OWNER, INDEX, 60).unwrap(),
+        SetWithdrawDelay::DISCRIMINATOR,
+    );
+    let parsed = SetWithdrawDelayInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!((parsed.index, parsed.delay), (INDEX, 60));
+
+    let data = split(
+        &set_rate_limit(&OWNER, &OWNER, INDEX, 100, VaultState::PERIOD_EPOCH).unwrap(),
+        SetRateLimit::DISCRIMINATOR,
+    );
+    let parsed = SetRateLimitInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(
+        (parsed.index, parsed.limit, parsed.period),
+        (INDEX, 100, VaultState::PERIOD_EPOCH)
+    );
+
+    let data = split(
+        &set_inheritance(&OWNER, &OWNER, INDEX, &DELEGATE, 60).unwrap(),
+        SetInheritance::DISCRIMINATOR,
+    );
+    let parsed = SetInheritanceInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(
+        (parsed.index, parsed.heir, parsed.inactivity_period),
+        (INDEX, DELEGATE, 60)
+    );
+
+    let data = split(
+        &set_freeze_authority(&OWNER, &OWNER, INDEX, &DELEGATE).unwrap(),
+        SetFreezeAuthority::DISCRIMINATOR,
+    );
+    let parsed = SetFreezeAuthorityInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!((parsed.index, parsed.freeze_authority), (INDEX, DELEGATE));
+
+    let data = split(
+        &heartbeat(&OWNER, &OWNER, INDEX).unwrap(),
+        Heartbeat::DISCRIMINATOR,
+    );
+    assert_eq!(
+        HeartbeatInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .index,
+        INDEX
+    );
+}
+
+#[test]
+fn allowlist_builders_match_the_parser() {
+    let (vault, _) = vault_and_state();
+    let (allowlist, _) = allowlist_address(&vault).unwrap();
+
+    let instruction = add_destination(&OWNER, &OWNER, INDEX, &DELEGATE).unwrap();
+    let data = split(&instruction, AddDestination::DISCRIMINATOR);
+    let parsed = AddDestinationInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!((parsed.index, parsed.destination), (INDEX, DELEGATE));
+    assert_eq!(instruction.accounts[3].pubkey, allowlist);
+
+    let instruction = remove_destination(&OWNER, &OWNER, INDEX, &DELEGATE).unwrap();
+    let data = split(&instruction, RemoveDestination::DISCRIMINATOR);
+    let parsed = RemoveDestinationInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!((parsed.index, parsed.destination), (INDEX, DELEGATE));
+    assert_eq!(instruction.accounts[3].pubkey, allowlist);
+}
+
+#[test]
+fn ownership_builders_match_the_parser() {
+    let data = split(
+        &transfer_ownership(&OWNER, &OWNER, INDEX, &DELEGATE).unwrap(),
+        TransferOwnership::DISCRIMINATOR,
+    );
+    let parsed = TransferOwnershipInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!((parsed.index, parsed.new_owner), (INDEX, DELEGATE));
+
+    let instruction = accept_ownership(&DELEGATE, &OWNER, INDEX).unwrap();
+    let data = split(&instruction, AcceptOwnership::DISCRIMINATOR);
+    assert_eq!(
+        AcceptOwnershipInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .index,
+        INDEX
+    );
+    assert!(instruction.accounts[0].is_signer);
+}
+
+#[test]
+fn recovery_builders_match_the_parser() {
+    let (vault, _) = vault_and_state();
+    let (recovery, _) = recovery_address(&vault).unwrap();
+    let guardians = [[3; 32], [4; 32]];
+
+    let data = split(
+        &set_guardians(&OWNER, &OWNER, INDEX, 2, 60, &guardians).unwrap(),
+        SetGuardians::DISCRIMINATOR,
+    );
+    let parsed = SetGuardiansInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(
+        (
+            parsed.index,
+            parsed.threshold,
+            parsed.challenge_period,
+            parsed.guardian_count
+        ),
+        (INDEX, 2, 60, 2)
+    );
+    assert_eq!(parsed.guardians[..2], guardians);
+
+    let instruction = propose_recovery(&DELEGATE, &OWNER, INDEX, &[5; 32], &guardians).unwrap();
+    let data = split(&instruction, ProposeRecovery::DISCRIMINATOR);
+    let parsed = ProposeRecoveryInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!((parsed.index, parsed.new_owner), (INDEX, [5; 32]));
+    assert_eq!(instruction.accounts[3].pubkey, recovery);
+    assert!(instruction.accounts[5..]
+        .iter()
+        .all(|guardian| guardian.is_signer));
+
+    let data = split(
+        &cancel_recovery(&OWNER, &OWNER, INDEX, &DELEGATE).unwrap(),
+        CancelRecovery::DISCRIMINATOR,
+    );
+    assert_eq!(
+        CancelRecoveryInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .index,
+        INDEX
+    );
+
+    let instruction = execute_recovery(&OWNER, INDEX, &DELEGATE).unwrap();
+    let data = split(&instruction, ExecuteRecovery::DISCRIMINATOR);
+    assert_eq!(
+        ExecuteRecoveryInstructionData::try_from(data.as_slice())
+            .unwrap()
+            .index,
+        INDEX
+    );
+    assert_eq!(instruction.accounts[2].pubkey, recovery);
+}
+
+#[test]
+fn close_and_freeze_builders_match_the_parser() {
+    let closing = [[3; 32], [4; 32]];
+    let tokens = [([5; 32], token::TOKEN_PROGRAM_ID)];
+    let instruction = close(&OWNER, &OWNER, INDEX, &closing, &tokens).unwrap();
+    let data = split(&instruction, Close::DISCRIMINATOR);
+    let parsed = CloseInstructionData::try_from(data.as_slice()).unwrap();
+    assert_eq!(
+        (parsed.index, parsed.close_count, parsed.token_count),
+        (INDEX, 2, 1)
+    );
+    assert_eq!(instruction.accounts.len(), 4 + closing.len() + 2);
+    let (vault, _) = vault_and_state();
+    assert_eq!(
+        instruction.accounts[6].pubkey,
+        associated_token_address(&vault, &[5; 32], &token::TOKEN_PROGRAM_ID)
+            .unwrap()
+            .0
+    );
+    assert_eq!(instruction.accounts[7].pubkey, token::TOKEN_PROGRAM_ID);
+
+    for (instruction, discriminator) in [
+        (freeze(&DELEGATE, &OWNER, INDEX), Freeze::DISCRIMINATOR),
+        (thaw(&DELEGATE, &OWNER, INDEX), Thaw::DISCRIMINATOR),
+    ] {
+        let instruction = instruction.unwrap();
+        let data = split(&instruction, discriminator);
+        assert_eq!(
+            FreezeInstructionData::try_from(data.as_slice())
+                .unwrap()
+                .index,
+            INDEX
+        );
+        assert_eq!(instruction.accounts[0].pubkey, DELEGATE);
+    }
+
+    let instruction = get_vested(&OWNER, &OWNER, INDEX).unwrap();
+    assert_eq!(instruction.data, [*GetVested::DISCRIMINATOR]);
+    assert_eq!(instruction.accounts[1].pubkey, vault_and_state().1);
+}
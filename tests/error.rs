@@ -0,0 +1,45 @@
+// Error codes are part of the program's interface: clients decode `Custom(code)` with
+// `VaultError::try_from`, so every code has to map back to the variant that produced it.
+
+use blueshift_vault::VaultError;
+use pinocchio::program_error::ProgramError;
+
+// One past the last assigned code.
+const CODES: u32 = 43;
+
+#[test]
+fn every_code_round_trips() {
+    for code in 0..CODES {
+        let error = VaultError::try_from(code).unwrap();
+        assert_eq!(error as u32, code);
+        assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+    }
+}
+
+#[test]
+fn unknown_codes_are_rejected() {
+    assert_eq!(
+        VaultError::try_from(CODES),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(
+        VaultError::try_from(u32::MAX),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn every_error_has_its_own_message() {
+    let messages: Vec<&str> = (0..CODES)
+        .map(|code| VaultError::try_from(code).unwrap().message())
+        .collect();
+
+    for (code, message) in messages.iter().enumerate() {
+        assert!(!message.is_empty(), "code {code} has no message");
+        assert_eq!(
+            messages.iter().filter(|other| other.eq(&message)).count(),
+            1,
+            "code {code} shares its message"
+        );
+    }
+}